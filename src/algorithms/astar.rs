@@ -0,0 +1,160 @@
+use crate::{
+    error::{GraphError, GraphResult},
+    graph::{Base, Count, EdgeCost, IndexAdjacent, IterAdjacent, Sortable, Tolerance},
+    prelude::NodeId,
+    structures::Distances,
+};
+use priq::PriorityQueue;
+use std::ops::Add;
+
+pub fn astar_between<N, W, C, G>(
+    graph: &G,
+    from: NodeId<G::Id>,
+    to: NodeId<G::Id>,
+    heuristic: impl Fn(NodeId<G::Id>) -> C,
+) -> GraphResult<Option<C>>
+where
+    C: Default + Sortable + Tolerance + Copy + Add<C, Output = C>,
+    W: EdgeCost<Cost = C>,
+    G: IndexAdjacent + Count + IterAdjacent + Base<Node = N, Weight = W>,
+{
+    let distances = astar(graph, from, to, heuristic)?;
+    Ok(distances.and_then(|distances| distances.distance(to).cloned()))
+}
+
+/// Runs dijkstra directed towards `to`, the nodes are settled by their distance plus the
+/// estimated cost of `heuristic` to reach `to`.
+/// The heuristic must be consistent, it never decreases by more than the cost of an edge
+/// and is zero at `to`. Violations up to rounding errors fail with
+/// [GraphError::InconsistentHeuristic] and [GraphError::HeuristicAtTarget].
+pub fn astar<N, W, C, G>(
+    graph: &G,
+    from: NodeId<G::Id>,
+    to: NodeId<G::Id>,
+    heuristic: impl Fn(NodeId<G::Id>) -> C,
+) -> GraphResult<Option<Distances<W::Cost, G>>>
+where
+    C: Default + Sortable + Tolerance + Copy + Add<C, Output = C>,
+    W: EdgeCost<Cost = C>,
+    G: IndexAdjacent + Count + IterAdjacent + Base<Node = N, Weight = W>,
+{
+    let target_estimate = heuristic(to);
+    if !target_estimate.le_approx(&C::default()) || !C::default().le_approx(&target_estimate) {
+        return Err(GraphError::HeuristicAtTarget(to.as_usize()));
+    }
+
+    let mut priority_queue = PriorityQueue::new();
+    let mut distances = Distances::with_count(graph.node_bound());
+
+    distances.add_cost(from, C::default());
+    priority_queue.put(heuristic(from), (from, C::default()));
+
+    while let Some((_, (node, dist))) = priority_queue.pop() {
+        if node == to {
+            return Ok(Some(distances));
+        }
+
+        if let Some(d) = distances.distance(node) && dist > *d {
+            continue;
+        }
+
+        let estimate = heuristic(node);
+
+        for edge in graph.iter_adjacent_edges(node) {
+            let to = edge.edge_id.to();
+            let cost = *edge.weight.cost();
+            let to_estimate = heuristic(to);
+
+            if !estimate.le_approx(&(cost + to_estimate)) {
+                return Err(GraphError::InconsistentHeuristic {
+                    from: node.as_usize(),
+                    to: to.as_usize(),
+                });
+            }
+
+            let next_dist = dist + cost;
+
+            let visited_or_geq = match distances.distance(to) {
+                Some(d) => next_dist >= *d,
+                None => false,
+            };
+
+            if !visited_or_geq {
+                distances.insert(node, to, next_dist);
+                priority_queue.put(next_dist + to_estimate, (to, next_dist));
+            }
+        }
+    }
+
+    Ok(None)
+}
+
+#[cfg(test)]
+mod test {
+    extern crate test;
+
+    use crate::{
+        algorithms::{_dijkstra, astar_between, dijkstra_between},
+        error::GraphError,
+        prelude::*,
+        test::{digraph, id, undigraph},
+    };
+    use test::Bencher;
+
+    #[test]
+    fn astar_zero_heuristic_matches_dijkstra() {
+        let graph: AdjacencyList<_, _, true> = digraph("data/Wege1.txt").unwrap();
+        assert_eq!(
+            astar_between(&graph, id(2), id(0), |_| 0.0).unwrap(),
+            Some(6.0)
+        );
+
+        let graph: AdjacencyList<_, _, true> = digraph("data/G_1_2.txt").unwrap();
+        assert_eq!(
+            astar_between(&graph, id(0), id(1), |_| 0.0).unwrap(),
+            dijkstra_between(&graph, id(0), id(1))
+        );
+    }
+
+    #[test]
+    fn astar_inconsistent_heuristic() {
+        let graph: AdjacencyList<_, _, true> = digraph("data/Wege1.txt").unwrap();
+
+        let heuristic = |node| if node == id(2) { 100.0 } else { 0.0 };
+        assert!(matches!(
+            astar_between(&graph, id(2), id(0), heuristic),
+            Err(GraphError::InconsistentHeuristic { from: 2, .. })
+        ));
+
+        assert!(matches!(
+            astar_between(&graph, id(2), id(0), |_| 1.0),
+            Err(GraphError::HeuristicAtTarget(0))
+        ));
+    }
+
+    #[test]
+    fn astar_exact_heuristic() {
+        // the exact distances are consistent, up to the rounding of their sums
+        let graph: AdjacencyList<_, _> = undigraph("data/G_1_2.txt").unwrap();
+        let exact = _dijkstra(&graph, id(1), None).unwrap();
+
+        let total = astar_between(&graph, id(0), id(1), |node| *exact.distance(node).unwrap());
+        assert_eq!(total.unwrap(), exact.distance(id(0)).cloned());
+    }
+
+    #[bench]
+    fn astar_g_1_2_undi_adj_list(b: &mut Bencher) {
+        let graph: AdjacencyList<_, _> = undigraph("data/G_1_2.txt").unwrap();
+        // the exact distances to the target are the best possible heuristic
+        let exact = _dijkstra(&graph, id(1), None).unwrap();
+
+        b.iter(|| {
+            let total = graph
+                .astar(id(0), id(1), |node| *exact.distance(node).unwrap())
+                .unwrap()
+                .and_then(|distances| distances.distance(id(1)).cloned())
+                .unwrap();
+            assert_eq!(total as f32, 2.36802)
+        })
+    }
+}
@@ -28,7 +28,7 @@ where
 {
     let mut bf = BellmanFord::init(graph, start);
 
-    for _ in 1..graph.node_count() {
+    for _ in 0..graph.node_count() {
         bf.updated = false;
         bf.relax();
         if !bf.updated {
@@ -60,7 +60,7 @@ where
 {
     let mut bf = BellmanFord::init(graph, start);
 
-    for _ in 1..graph.node_count() {
+    for _ in 0..graph.node_count() {
         bf.updated = false;
         bf.relax();
         if !bf.updated {
@@ -85,7 +85,7 @@ struct BellmanFord<'a, C, G: Base<Weight: EdgeCost<Cost = C>>> {
 
 impl<'a, C: Default + Clone, G: Base<Weight: EdgeCost<Cost = C>> + Count> BellmanFord<'a, C, G> {
     fn init(graph: &'a G, start: NodeId<G::Id>) -> Self {
-        let count = graph.node_count();
+        let count = graph.node_bound();
         let mut distances = Distances::with_count(count);
 
         distances.add_cost(start, C::default());
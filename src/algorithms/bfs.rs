@@ -10,7 +10,7 @@ where
     G: Index + IndexAdjacent + Count,
 {
     let mut counter = 0;
-    let mut markers = vec![0; graph.node_count()];
+    let mut markers = vec![0; graph.node_bound()];
     let mut components = Vec::new();
 
     for from in graph.node_ids() {
@@ -28,7 +28,7 @@ pub fn bfs<G>(graph: &G, from: NodeId<G::Id>) -> Tree<G>
 where
     G: IndexAdjacent + Count,
 {
-    let mut markers = vec![false; graph.node_count()];
+    let mut markers = vec![false; graph.node_bound()];
     bfs_marker(graph, from, &mut markers, true)
 }
 
@@ -36,7 +36,7 @@ pub fn bfs_iter<G>(graph: &G, from: NodeId<G::Id>) -> impl Iterator<Item = NodeI
 where
     G: IndexAdjacent + Count,
 {
-    let mut visited = vec![false; graph.node_count()];
+    let mut visited = vec![false; graph.node_bound()];
     let mut queue = VecDeque::new();
 
     queue.push_back(from);
@@ -61,7 +61,7 @@ pub fn bfs_iter_edges<G>(graph: &G, from: NodeId<G::Id>) -> impl Iterator<Item =
 where
     G: IndexAdjacent + Count,
 {
-    let mut visited = vec![false; graph.node_count()];
+    let mut visited = vec![false; graph.node_bound()];
     let mut queue = VecDeque::new();
 
     queue.push_back(from);
@@ -91,7 +91,7 @@ where
     F: FnMut(&W) -> bool,
     G: IndexAdjacent + Count + Get + Base<Node = N, Weight = W>,
 {
-    let count = graph.node_count();
+    let count = graph.node_bound();
     let mut queue = VecDeque::new();
     let mut visited = vec![false; count];
     let mut parents = Parents::with_count(count);
@@ -148,7 +148,7 @@ where
 #[cfg(test)]
 mod test {
     extern crate test;
-    use crate::{prelude::*, test::weightless_undigraph};
+    use crate::{algorithms::bfs_scc, prelude::*, test::weightless_undigraph};
     use test::Bencher;
 
     #[bench]
@@ -214,6 +214,27 @@ mod test {
         });
     }
 
+    #[bench]
+    fn bfs_scc_graph_gross_csr(b: &mut Bencher) {
+        let graph: CsrGraph<_, _> = weightless_undigraph("data/Graph_gross.txt").unwrap();
+
+        b.iter(|| {
+            let counter = bfs_scc(&graph).len();
+            assert_eq!(counter, 222);
+        });
+    }
+
+    #[cfg(feature = "extensive")]
+    #[bench]
+    fn bfs_scc_graph_ganz_gross_csr(b: &mut Bencher) {
+        let graph: CsrGraph<_, _> = weightless_undigraph("data/Graph_ganzgross.txt").unwrap();
+
+        b.iter(|| {
+            let counter = bfs_scc(&graph).len();
+            assert_eq!(counter, 9560);
+        });
+    }
+
     #[bench]
     fn bfs_scc_graph1_adj_mat(b: &mut Bencher) {
         let graph: AdjacencyMatrix<_, _> = weightless_undigraph("data/Graph1.txt").unwrap();
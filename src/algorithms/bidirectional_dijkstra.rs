@@ -0,0 +1,270 @@
+use crate::{
+    graph::{Base, Count, Directed, EdgeCost, Iter, IterAdjacent, Sortable},
+    prelude::NodeId,
+    structures::{Distances, Route},
+};
+use priq::PriorityQueue;
+use std::ops::Add;
+
+/// One direction of the search with its own queue and parents
+struct Search<C, G>
+where
+    C: PartialOrd,
+    G: Base,
+    G::Weight: EdgeCost<Cost = C>,
+{
+    priority_queue: PriorityQueue<C, NodeId<G::Id>>,
+    distances: Distances<C, G>,
+}
+
+impl<C, G> Search<C, G>
+where
+    C: Default + Sortable + Copy + Add<C, Output = C>,
+    G: Base<Weight: EdgeCost<Cost = C>>,
+{
+    fn new(count: usize, start: NodeId<G::Id>) -> Self {
+        let mut priority_queue = PriorityQueue::new();
+        let mut distances = Distances::with_count(count);
+
+        distances.add_cost(start, C::default());
+        priority_queue.put(C::default(), start);
+
+        Self {
+            priority_queue,
+            distances,
+        }
+    }
+
+    fn peek(&self) -> Option<C> {
+        self.priority_queue.peek().map(|(dist, _)| *dist)
+    }
+
+    /// Pops the next node that is not outdated
+    fn pop(&mut self) -> Option<(C, NodeId<G::Id>)> {
+        while let Some((dist, node)) = self.priority_queue.pop() {
+            match self.distances.distance(node) {
+                Some(d) if dist > *d => continue,
+                _ => return Some((dist, node)),
+            }
+        }
+
+        None
+    }
+
+    /// Relaxes the edges of `node` and records paths that meet the other search
+    fn relax(
+        &mut self,
+        node: NodeId<G::Id>,
+        dist: C,
+        edges: impl Iterator<Item = (NodeId<G::Id>, C)>,
+        other: &Distances<C, G>,
+        best: &mut Option<(C, NodeId<G::Id>)>,
+    ) {
+        for (to, cost) in edges {
+            let next_dist = dist + cost;
+
+            if let Some(rest) = other.distance(to)
+                && best.is_none_or(|(total, _)| next_dist + *rest < total)
+            {
+                *best = Some((next_dist + *rest, to));
+            }
+
+            let visited_or_geq = match self.distances.distance(to) {
+                Some(d) => next_dist >= *d,
+                None => false,
+            };
+
+            if !visited_or_geq {
+                self.distances.insert(node, to, next_dist);
+                self.priority_queue.put(next_dist, to);
+            }
+        }
+    }
+}
+
+/// The incoming edges of every node with their source and cost
+type Reverse<C, Id> = Vec<Vec<(NodeId<Id>, C)>>;
+
+/// Bidirectional dijkstra queries on one graph, see [bidirectional_dijkstra].
+/// The reversed adjacency of a directed graph is built once and shared by all queries.
+pub struct BidirectionalDijkstra<'a, C, G: Base> {
+    graph: &'a G,
+    reverse: Option<Reverse<C, G::Id>>,
+}
+
+impl<'a, N, W, C, G> BidirectionalDijkstra<'a, C, G>
+where
+    C: Default + Sortable + Copy + Add<C, Output = C>,
+    W: EdgeCost<Cost = C>,
+    G: Count + Directed + Iter + IterAdjacent + Base<Node = N, Weight = W>,
+{
+    pub fn new(graph: &'a G) -> Self {
+        let reverse = G::directed().then(|| {
+            let mut reverse = vec![Vec::new(); graph.node_bound()];
+            for edge in graph.iter_edges() {
+                reverse[edge.edge_id.to().as_usize()]
+                    .push((edge.edge_id.from(), *edge.weight.cost()));
+            }
+            reverse
+        });
+
+        Self { graph, reverse }
+    }
+
+    /// The shortest route from `from` to `to` with its cost, `None` if `to` is not reachable
+    pub fn query(&self, from: NodeId<G::Id>, to: NodeId<G::Id>) -> Option<(Route<G>, C)> {
+        let graph = self.graph;
+        let mut forward = Search::<C, G>::new(graph.node_bound(), from);
+        let mut backward = Search::<C, G>::new(graph.node_bound(), to);
+        let mut best = (from == to).then_some((C::default(), from));
+
+        // a path through the nodes left in the queues costs at least the sum of their minimums
+        while let (Some(f), Some(b)) = (forward.peek(), backward.peek()) {
+            if let Some((total, _)) = best && f + b >= total {
+                break;
+            }
+
+            if f <= b {
+                let Some((dist, node)) = forward.pop() else {
+                    break;
+                };
+                let edges = graph
+                    .iter_adjacent_edges(node)
+                    .map(|edge| (edge.edge_id.to(), *edge.weight.cost()));
+                forward.relax(node, dist, edges, &backward.distances, &mut best);
+            } else {
+                let Some((dist, node)) = backward.pop() else {
+                    break;
+                };
+                match &self.reverse {
+                    Some(reverse) => {
+                        let edges = reverse[node.as_usize()].iter().copied();
+                        backward.relax(node, dist, edges, &forward.distances, &mut best);
+                    }
+                    None => {
+                        let edges = graph
+                            .iter_adjacent_edges(node)
+                            .map(|edge| (edge.edge_id.to(), *edge.weight.cost()));
+                        backward.relax(node, dist, edges, &forward.distances, &mut best);
+                    }
+                }
+            }
+        }
+
+        let (total, meeting) = best?;
+
+        let mut route = forward.distances.parents.path(from, meeting)?.into_raw();
+        let back = backward.distances.parents.path(to, meeting)?.into_raw();
+        route.extend(back.into_iter().rev().skip(1));
+
+        Some((Route::new(route), total))
+    }
+}
+
+/// Searches forward from `from` and backward from `to` until the frontiers meet.
+/// Directed graphs are searched backward on a reversed copy of their adjacency,
+/// use [BidirectionalDijkstra] to build it once for many queries.
+/// Like `dijkstra` the costs must not be negative.
+pub fn bidirectional_dijkstra<N, W, C, G>(
+    graph: &G,
+    from: NodeId<G::Id>,
+    to: NodeId<G::Id>,
+) -> Option<(Route<G>, C)>
+where
+    C: Default + Sortable + Copy + Add<C, Output = C>,
+    W: EdgeCost<Cost = C>,
+    G: Count + Directed + Iter + IterAdjacent + Base<Node = N, Weight = W>,
+{
+    BidirectionalDijkstra::new(graph).query(from, to)
+}
+
+#[cfg(test)]
+mod test {
+    extern crate test;
+
+    use crate::{
+        algorithms::{bidirectional_dijkstra, dijkstra_between, BidirectionalDijkstra},
+        graph::{Base, Count, Create, Directed, Get, IndexAdjacent, Insert, Iter, IterAdjacent},
+        prelude::*,
+        test::{digraph, id, undigraph},
+    };
+    use test::Bencher;
+
+    fn assert_matches<G>(graph: &G, from: usize, to: usize)
+    where
+        G: Count
+            + Directed
+            + Get
+            + IndexAdjacent
+            + Iter
+            + IterAdjacent
+            + Base<Id = usize, Weight = f64>,
+    {
+        let expected = dijkstra_between(graph, id(from), id(to));
+        let result = bidirectional_dijkstra(graph, id(from), id(to));
+
+        let Some((route, total)) = result else {
+            assert_eq!(expected, None);
+            return;
+        };
+
+        let cost = route
+            .edge_ids()
+            .map(|edge_id| graph.weight(edge_id).unwrap())
+            .sum::<f64>();
+
+        // the halves are summed in another order than by dijkstra
+        assert_eq!(expected.map(|cost| cost as f32), Some(total as f32));
+        assert_eq!(cost as f32, total as f32);
+        assert_eq!(route.first(), Some(id(from)));
+        assert_eq!(route.last(), Some(id(to)));
+    }
+
+    #[test]
+    fn bidirectional_dijkstra_matches_dijkstra() {
+        // the other Wege graphs have negative costs, which neither search supports
+        for path in ["data/G_1_2.txt", "data/G_1_20.txt", "data/Wege1.txt"] {
+            let graph: AdjacencyList<usize, f64, true> = digraph(path).unwrap();
+            let matrix: AdjacencyMatrix<usize, f64, true> = digraph(path).unwrap();
+            let undirected: AdjacencyList<usize, f64> = undigraph(path).unwrap();
+
+            for (from, to) in [(0, 1), (1, 0), (2, 0), (0, 2), (1, 1)] {
+                assert_matches(&graph, from, to);
+                assert_matches(&matrix, from, to);
+                assert_matches(&undirected, from, to);
+            }
+        }
+    }
+
+    #[test]
+    fn bidirectional_dijkstra_unreachable() {
+        let mut graph = AdjacencyList::<usize, f64, true>::with_nodes(0..3);
+        graph.insert_edge(id(0), id(1), 1.0);
+
+        assert!(bidirectional_dijkstra(&graph, id(0), id(1)).is_some());
+        assert!(bidirectional_dijkstra(&graph, id(1), id(0)).is_none());
+        assert!(bidirectional_dijkstra(&graph, id(0), id(2)).is_none());
+    }
+
+    #[bench]
+    fn bidirectional_dijkstra_g_1_2_undi_adj_list(b: &mut Bencher) {
+        let graph: AdjacencyList<_, _> = undigraph("data/G_1_2.txt").unwrap();
+
+        b.iter(|| {
+            let (_, total) = bidirectional_dijkstra(&graph, id(0), id(1)).unwrap();
+            assert_eq!(total as f32, 2.36802)
+        })
+    }
+
+    #[bench]
+    fn bidirectional_dijkstra_g_1_2_di_adj_list(b: &mut Bencher) {
+        let graph: AdjacencyList<_, _, true> = digraph("data/G_1_2.txt").unwrap();
+        let expected = dijkstra_between(&graph, id(0), id(2)).map(|cost| cost as f32);
+        let query = BidirectionalDijkstra::new(&graph);
+
+        b.iter(|| {
+            let total = query.query(id(0), id(2)).map(|(_, total)| total as f32);
+            assert_eq!(total, expected)
+        })
+    }
+}
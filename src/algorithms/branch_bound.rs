@@ -30,7 +30,7 @@ where
                 .map(|tour| tour.1)
                 .unwrap_or(Maximum::MAX);
             let mut path = vec![start];
-            let mut visited = vec![false; graph.node_count()];
+            let mut visited = vec![false; graph.node_bound()];
             let cost = C::default();
 
             _branch_bound_rec(
@@ -61,7 +61,7 @@ where
         .unwrap_or(Maximum::MAX);
     let mut route = Vec::new();
 
-    let mut visited = vec![false; graph.node_count()];
+    let mut visited = vec![false; graph.node_bound()];
     visited[start.as_usize()] = true;
 
     stack.push((C::default(), vec![start], visited));
@@ -1,36 +1,124 @@
-use std::cmp::min_by;
+use super::{_edmonds_karp, _ford_fulkerson, _residual_graph, augmenting_path, ResidualGraph};
+use crate::{
+    graph::{Base, EdgeCapacity, EdgeCost, EdgeFlow, Iter},
+    prelude::NodeId,
+};
+use num_traits::Float;
+use std::ops::{AddAssign, SubAssign};
 
-use num_traits::{Float, Pow};
-
-use crate::graph::{Base, EdgeCapacity, Iter, Sortable};
-
-pub fn capcity_scaling<N, W, C, G>(graph: &G) -> Option<C>
+/// Maximum flow by augmenting only along paths whose residual capacities are at least delta.
+/// Delta starts at the largest power of two not greater than the maximal capacity
+/// and is halved whenever no more such paths are found.
+/// Returns the final residual graph and the value of the flow.
+pub fn capacity_scaling<N, W, C, G>(
+    graph: &G,
+    source: NodeId<G::Id>,
+    sink: NodeId<G::Id>,
+) -> Option<(ResidualGraph<N, C>, C)>
 where
-    C: Float,
-    W: EdgeCapacity<Capacity = C>,
-    G: Iter + Base<Node = N, Weight = W>,
+    N: Clone,
+    C: Float + Default + AddAssign + SubAssign,
+    W: EdgeCapacity<Capacity = C> + EdgeCost<Cost = C> + EdgeFlow<Flow = C>,
+    G: Iter + Base<Id = usize, Node = N, Weight = W>,
 {
-    let delta = match graph
+    // cost only weights have an infinite capacity, which must not be scaled
+    let max = graph
         .iter_edges()
-        .map(|edge| edge.weight.capacity())
-        .cloned()
-        .reduce(C::max)
-    {
-        Some(max) => {
-            let mut delta = C::one();
-            while delta.powi(2) < max {
-                delta = delta.powi(2)
-            }
-            delta
-        }
-        None => return None,
+        .map(|edge| *edge.weight.capacity())
+        .filter(|capacity| capacity.is_finite())
+        .reduce(C::max)?;
+
+    let mut delta = max.log2().floor().exp2();
+    let mut residual_graph = _residual_graph(graph);
+    let mut total_flow = C::default();
+
+    while delta >= C::one() {
+        total_flow += _ford_fulkerson(&mut residual_graph, source, sink, |graph, source, sink| {
+            augmenting_path(graph, source, sink, |weight| {
+                (weight.capacity - weight.flow) >= delta
+            })
+        });
+        delta = delta / (C::one() + C::one());
+    }
+
+    // capacities are not necessarily integral, so the remaining flow is augmented unscaled
+    total_flow += _edmonds_karp(&mut residual_graph, source, sink);
+
+    Some((residual_graph, total_flow))
+}
+
+#[cfg(test)]
+mod test {
+    extern crate test;
+
+    use super::capacity_scaling;
+    use crate::{
+        algorithms::is_reverse_arc,
+        graph::{Create, FlowWeight, Insert, Iter, IterAdjacent},
+        prelude::*,
+        test::{digraph, id},
     };
+    use test::Bencher;
+
+    fn flow_graph(path: &str) -> AdjacencyList<usize, FlowWeight<f64>, true> {
+        let graph: AdjacencyList<_, f64, true> = digraph(path).unwrap();
+        let mut flow_graph = AdjacencyList::with_nodes(graph.iter_nodes().cloned());
+
+        for EdgeRef { edge_id, weight } in graph.iter_edges() {
+            flow_graph.insert_edge(
+                edge_id.from(),
+                edge_id.to(),
+                FlowWeight::new(*weight, 0.0, 0.0),
+            );
+        }
+
+        flow_graph
+    }
+
+    #[test]
+    fn capacity_scaling_fluss_residual_graph() {
+        let graph = flow_graph("data/Fluss.txt");
+        let (residual_graph, total) = capacity_scaling(&graph, id(0), id(7)).unwrap();
+
+        let outflow = residual_graph
+            .iter_adjacent_edges(id(0))
+            .filter(|edge| !is_reverse_arc(edge.edge_id))
+            .fold(0.0, |akku, edge| akku + edge.weight.flow);
+
+        assert_eq!(outflow, total);
+
+        for EdgeRef { edge_id: _, weight } in residual_graph.iter_edges() {
+            assert!(weight.flow <= weight.capacity);
+        }
+    }
+
+    #[bench]
+    fn capacity_scaling_g_1_2_adj_list(b: &mut Bencher) {
+        let graph = flow_graph("data/G_1_2.txt");
+
+        b.iter(|| {
+            let total = graph.capacity_scaling(id(0), id(7)).unwrap().1;
+            assert_eq!(total as f32, 0.75447)
+        })
+    }
+
+    #[bench]
+    fn capacity_scaling_fluss_adj_list(b: &mut Bencher) {
+        let graph = flow_graph("data/Fluss.txt");
+
+        b.iter(|| {
+            let total = graph.capacity_scaling(id(0), id(7)).unwrap().1;
+            assert_eq!(total as f32, 4.0)
+        })
+    }
 
-    // TODO find augmenting paths where all edge capacities are >= delta
-    // if no more paths are found: delta = delta / 2 and repeat
-    // find bottleneck of that path
-    // apply bottleneck
-    // when delta == 0 terminate
+    #[bench]
+    fn capacity_scaling_fluss2_adj_list(b: &mut Bencher) {
+        let graph = flow_graph("data/Fluss2.txt");
 
-    todo!()
+        b.iter(|| {
+            let total = graph.capacity_scaling(id(0), id(7)).unwrap().1;
+            assert_eq!(total as f32, 5.0)
+        })
+    }
 }
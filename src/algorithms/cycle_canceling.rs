@@ -1,9 +1,11 @@
-use super::{bellman_ford_cycle, Mcf};
-use crate::graph::{
-    Base, Count, EdgeCapacity, EdgeCost, EdgeDirection, EdgeFlow, Get, GetMut, Index,
-    IndexAdjacent, Insert, Iter, IterAdjacent, Maximum, NodeBalance, Remove,
+use super::{is_reverse_arc, reverse_arc, Mcf, ResidualGraph};
+use crate::{
+    graph::{
+        Base, Count, EdgeCapacity, EdgeCost, EdgeFlow, Get, GetMut, Index, Iter, Maximum,
+        NodeBalance,
+    },
+    prelude::{EdgeId, EdgeRef, NodeId},
 };
-use either::Either;
 use std::{
     fmt::Debug,
     ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign},
@@ -11,12 +13,8 @@ use std::{
 
 pub fn cycle_canceling<N, W, C, G>(graph: &G) -> C
 where
-    N: Default + NodeBalance<Balance = C>,
-    W: Default
-        + EdgeCapacity<Capacity = C>
-        + EdgeCost<Cost = C>
-        + EdgeDirection
-        + EdgeFlow<Flow = C>,
+    N: Default + Clone + NodeBalance<Balance = C>,
+    W: EdgeCapacity<Capacity = C> + EdgeCost<Cost = C> + EdgeFlow<Flow = C>,
     C: Maximum
         + Default
         + PartialOrd
@@ -28,19 +26,7 @@ where
         + Mul<C, Output = C>
         + Sub<C, Output = C>
         + Debug,
-    G: Index
-        + Get
-        + GetMut
-        + Insert
-        + Remove
-        + Count
-        + IndexAdjacent
-        + Index
-        + Iter
-        + IterAdjacent
-        + Base<Node = N, Weight = W>
-        + Clone
-        + Debug,
+    G: Index + Iter + Base<Id = usize, Node = N, Weight = W>,
 {
     let mut mcf = Mcf::init(graph);
     assert!(mcf.solvable());
@@ -53,10 +39,10 @@ where
     let mut total_flow = C::default();
 
     for start in graph.node_ids() {
-        while let Either::Right(cycle) = bellman_ford_cycle(&residual_graph, start) {
+        while let Some(cycle) = negative_cycle(&residual_graph, start) {
             let mut bottleneck = C::MAX;
 
-            for edge_id in cycle.edge_id_cycle() {
+            for &edge_id in &cycle {
                 let weight = residual_graph.weight(edge_id).unwrap();
                 let residual_capacity = *weight.capacity() - *weight.flow();
 
@@ -72,15 +58,13 @@ where
 
             total_flow += bottleneck;
 
-            for edge_id in cycle.edge_id_cycle() {
+            for edge_id in cycle {
                 let weight = residual_graph.weight_mut(edge_id).unwrap();
                 *weight.flow_mut() += bottleneck;
-                // *weight.capacity_mut() -= bottleneck;
                 assert!(weight.flow() >= &C::default());
 
-                let weight_rev = residual_graph.weight_mut(edge_id.rev()).unwrap();
+                let weight_rev = residual_graph.weight_mut(reverse_arc(edge_id)).unwrap();
                 *weight_rev.flow_mut() -= bottleneck;
-                // *weight_rev.capacity_mut() += bottleneck;
                 assert!(weight_rev.flow() >= &C::default());
             }
         }
@@ -90,7 +74,7 @@ where
         .iter_edges()
         .fold(C::default(), |mut akku, edge| {
             let weight = edge.weight;
-            if !weight.is_reverse() {
+            if !is_reverse_arc(edge.edge_id) {
                 akku += *weight.flow() * *weight.cost();
             }
 
@@ -100,10 +84,78 @@ where
     cost
 }
 
+/// Bellman-Ford over the arcs with residual capacity left.
+/// Returns the arcs of a negative cycle reachable from `start`.
+fn negative_cycle<N, C>(
+    graph: &ResidualGraph<N, C>,
+    start: NodeId<usize>,
+) -> Option<Vec<EdgeId<usize>>>
+where
+    C: Default + PartialOrd + Copy + Add<C, Output = C> + Sub<C, Output = C>,
+{
+    let count = graph.node_bound();
+    let mut distances = vec![None; count];
+    let mut parents: Vec<Option<EdgeId<usize>>> = vec![None; count];
+    let mut updated = None;
+
+    distances[start.as_usize()] = Some(C::default());
+
+    for _ in 0..count {
+        updated = None;
+
+        for EdgeRef { edge_id, weight } in graph.iter_edges() {
+            let Some(cost) = distances[edge_id.from().as_usize()] else {
+                continue;
+            };
+
+            let to = edge_id.to();
+            let combined_cost = cost + *weight.cost();
+            let update = match distances[to.as_usize()] {
+                Some(c) => c > combined_cost,
+                None => true,
+            } && (*weight.capacity() - *weight.flow()) > C::default();
+
+            if update {
+                distances[to.as_usize()] = Some(combined_cost);
+                parents[to.as_usize()] = Some(edge_id);
+                updated = Some(to);
+            }
+        }
+
+        updated?;
+    }
+
+    // still updated after `count` rounds, so walking back `count` arcs ends on the cycle
+    let mut node = updated?;
+    for _ in 0..count {
+        node = parents[node.as_usize()]?.from();
+    }
+
+    let mut cycle = Vec::new();
+    let mut to = node;
+
+    loop {
+        let edge_id = parents[to.as_usize()]?;
+        cycle.push(edge_id);
+        to = edge_id.from();
+
+        if to == node {
+            break;
+        }
+    }
+
+    cycle.reverse();
+    Some(cycle)
+}
+
 #[cfg(test)]
 mod test {
     use super::cycle_canceling;
-    use crate::{prelude::AdjacencyList, test::bgraph};
+    use crate::{
+        graph::{BalancedNode, Create, FlowWeight, Insert},
+        prelude::AdjacencyList,
+        test::{bgraph, id},
+    };
 
     #[test]
     fn cycle_canceling_kostenminimal_1() {
@@ -153,4 +205,21 @@ mod test {
         let graph: AdjacencyList<_, _, true> = bgraph("data/Kostenminimal_gross3.txt").unwrap();
         let _cost = cycle_canceling(&graph);
     }
+
+    #[test]
+    fn cycle_canceling_anti_parallel_arcs() {
+        let nodes = [1.0, -1.0, 0.0]
+            .into_iter()
+            .enumerate()
+            .map(|(node, balance)| BalancedNode::new(node, balance));
+        let mut graph = AdjacencyList::<_, _, true>::with_nodes(nodes);
+
+        // the arc back from 1 to 0 must not serve as the reverse arc of the expensive one
+        graph.insert_edge(id(0), id(1), FlowWeight::new(1.0, 5.0, 0.0));
+        graph.insert_edge(id(1), id(0), FlowWeight::new(1.0, 1.0, 0.0));
+        graph.insert_edge(id(0), id(2), FlowWeight::new(1.0, 1.0, 0.0));
+        graph.insert_edge(id(2), id(1), FlowWeight::new(1.0, 1.0, 0.0));
+
+        assert_eq!(cycle_canceling(&graph), 2.0);
+    }
 }
@@ -9,7 +9,7 @@ where
     G: Index + IndexAdjacent + Count,
 {
     let mut counter = 0;
-    let mut markers = vec![0; graph.node_count()];
+    let mut markers = vec![0; graph.node_bound()];
     let mut components = Vec::new();
 
     for from in graph.node_ids() {
@@ -27,7 +27,7 @@ pub fn dfs<G>(graph: &G, from: NodeId<G::Id>) -> Tree<G>
 where
     G: IndexAdjacent + Count,
 {
-    let mut markers = vec![false; graph.node_count()];
+    let mut markers = vec![false; graph.node_bound()];
     dfs_marker(graph, from, &mut markers, true)
 }
 
@@ -35,7 +35,7 @@ pub fn dfs_iter<G>(graph: &G, from: NodeId<G::Id>) -> impl Iterator<Item = NodeI
 where
     G: IndexAdjacent + Count,
 {
-    let mut visited = vec![false; graph.node_count()];
+    let mut visited = vec![false; graph.node_bound()];
     let mut stack = Vec::new();
 
     stack.push(from);
@@ -60,7 +60,7 @@ pub fn dfs_iter_edges<G>(graph: &G, from: NodeId<G::Id>) -> impl Iterator<Item =
 where
     G: IndexAdjacent + Count,
 {
-    let mut visited = vec![false; graph.node_count()];
+    let mut visited = vec![false; graph.node_bound()];
     let mut stack = Vec::new();
 
     stack.push(from);
@@ -90,7 +90,7 @@ where
     F: FnMut(&W) -> bool,
     G: IndexAdjacent + Count + Get + Base<Node = N, Weight = W>,
 {
-    let count = graph.node_count();
+    let count = graph.node_bound();
     let mut stack = Vec::new();
     let mut visited = vec![false; count];
     let mut parents = Parents::with_count(count);
@@ -146,7 +146,7 @@ where
 #[cfg(test)]
 mod test {
     extern crate test;
-    use crate::{prelude::*, test::weightless_undigraph};
+    use crate::{algorithms::dfs_scc, prelude::*, test::weightless_undigraph};
     use test::Bencher;
 
     #[bench]
@@ -212,6 +212,27 @@ mod test {
         });
     }
 
+    #[bench]
+    fn dfs_scc_graph_gross_csr(b: &mut Bencher) {
+        let graph: CsrGraph<_, _> = weightless_undigraph("data/Graph_gross.txt").unwrap();
+
+        b.iter(|| {
+            let counter = dfs_scc(&graph).len();
+            assert_eq!(counter, 222);
+        });
+    }
+
+    #[cfg(feature = "extensive")]
+    #[bench]
+    fn dfs_scc_graph_ganz_gross_csr(b: &mut Bencher) {
+        let graph: CsrGraph<_, _> = weightless_undigraph("data/Graph_ganzgross.txt").unwrap();
+
+        b.iter(|| {
+            let counter = dfs_scc(&graph).len();
+            assert_eq!(counter, 9560);
+        });
+    }
+
     #[bench]
     fn dfs_scc_graph1_adj_mat(b: &mut Bencher) {
         let graph: AdjacencyMatrix<_, _> = weightless_undigraph("data/Graph1.txt").unwrap();
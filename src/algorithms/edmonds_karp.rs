@@ -4,18 +4,13 @@ use std::{
 };
 
 use crate::{
-    graph::{
-        Base, Count, Create, EdgeCapacity, EdgeCost, EdgeFlow, FlowWeight, Get, GetMut,
-        IndexAdjacent, Insert, Iter,
-    },
-    prelude::{AdjacencyList, EdgeRef, NodeId},
-    structures::Parents,
+    graph::{Base, Create, EdgeCost, FlowWeight, Get, Iter},
+    prelude::{EdgeRef, NodeId},
 };
 
-use super::{_ford_fulkerson, bfs_sp};
-
-// TODO fix edmonds karp to not use adj list but graph representation itself
+use super::{_ford_fulkerson, augmenting_path, insert_arc, ResidualGraph};
 
+/// Maximum flow, the costs of the edges are their capacities.
 pub fn edmonds_karp<N, W, C, G>(graph: &G, source: NodeId<G::Id>, sink: NodeId<G::Id>) -> C
 where
     N: Debug,
@@ -30,51 +25,29 @@ where
     W: EdgeCost<Cost = C>,
     G: Iter + Base<Id = usize> + Get + Base<Node = N, Weight = W> + Debug,
 {
-    let mut residual_graph = AdjacencyList::<_, _, true>::with_nodes(graph.iter_nodes());
+    let mut residual_graph = ResidualGraph::with_nodes(graph.iter_nodes());
     for EdgeRef { edge_id, weight } in graph.iter_edges() {
         let cost = *weight.cost();
-
         let capacity = FlowWeight::new(cost, cost, C::default());
-        residual_graph.insert_edge(edge_id.from(), edge_id.to(), capacity);
-
-        if !graph.contains_edge_id(edge_id.rev()) {
-            residual_graph.insert_edge(
-                edge_id.to(),
-                edge_id.from(),
-                FlowWeight::new(cost, -cost, cost),
-            );
-        }
+        insert_arc(&mut residual_graph, edge_id.from(), edge_id.to(), capacity);
     }
 
     _edmonds_karp(&mut residual_graph, source, sink)
 }
 
-pub(crate) fn _edmonds_karp<N, W, C, G>(
-    graph: &mut G,
-    source: NodeId<G::Id>,
-    sink: NodeId<G::Id>,
+pub(crate) fn _edmonds_karp<N, C>(
+    graph: &mut ResidualGraph<N, C>,
+    source: NodeId<usize>,
+    sink: NodeId<usize>,
 ) -> C
 where
     C: Default + PartialOrd + Copy + AddAssign + SubAssign + Sub<C, Output = C>,
-    W: EdgeCapacity<Capacity = C> + EdgeFlow<Flow = C>,
-    G: Count + IndexAdjacent + Get + GetMut + Base<Node = N, Weight = W> + Debug,
 {
-    fn shortest_path<N, W, C, G>(
-        graph: &G,
-        source: NodeId<G::Id>,
-        sink: NodeId<G::Id>,
-    ) -> Option<Parents<G>>
-    where
-        C: Default + Sub<C, Output = C> + PartialOrd + Copy,
-        W: EdgeCapacity<Capacity = C> + EdgeFlow<Flow = C>,
-        G: IndexAdjacent + Count + Get + Base<Node = N, Weight = W>,
-    {
-        bfs_sp(graph, source, sink, |weight: &W| {
-            (*weight.capacity() - *weight.flow()) > C::default()
+    _ford_fulkerson(graph, source, sink, |graph, source, sink| {
+        augmenting_path(graph, source, sink, |weight| {
+            (weight.capacity - weight.flow) > C::default()
         })
-    }
-
-    _ford_fulkerson(graph, source, sink, shortest_path)
+    })
 }
 
 #[cfg(test)]
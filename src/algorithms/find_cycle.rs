@@ -0,0 +1,367 @@
+use crate::{
+    graph::{Base, Count, Directed, Index, IndexAdjacent},
+    prelude::NodeId,
+    structures::Route,
+};
+
+pub fn has_cycle<G>(graph: &G) -> bool
+where
+    G: Index + IndexAdjacent + Count + Directed,
+{
+    find_cycle(graph).is_some()
+}
+
+/// Returns a cycle of the graph if there is one.
+/// In undirected graphs the edge back to the parent of a node is not considered a cycle.
+pub fn find_cycle<G>(graph: &G) -> Option<Route<G>>
+where
+    G: Index + IndexAdjacent + Count + Directed,
+{
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    enum State {
+        Unvisited,
+        Active,
+        Finished,
+    }
+
+    let mut states = vec![State::Unvisited; graph.node_bound()];
+
+    for root in graph.node_ids() {
+        if states[root.as_usize()] != State::Unvisited {
+            continue;
+        }
+
+        let mut stack = vec![(root, graph.adjacent_node_ids(root))];
+        states[root.as_usize()] = State::Active;
+
+        while let Some((from, adjacent)) = stack.last_mut() {
+            let from = *from;
+
+            match adjacent.next() {
+                Some(to) => match states[to.as_usize()] {
+                    State::Unvisited => {
+                        states[to.as_usize()] = State::Active;
+                        stack.push((to, graph.adjacent_node_ids(to)));
+                    }
+                    State::Active => {
+                        let parent = stack.len().checked_sub(2).map(|i| stack[i].0);
+
+                        if G::directed() || parent != Some(to) {
+                            let position = stack.iter().position(|(node, _)| *node == to).unwrap();
+                            let route = stack[position..].iter().map(|(node, _)| *node).collect();
+                            return Some(Route::new(route));
+                        }
+                    }
+                    State::Finished => (),
+                },
+                None => {
+                    states[from.as_usize()] = State::Finished;
+                    stack.pop();
+                }
+            }
+        }
+    }
+
+    None
+}
+
+/// Lazily enumerates all elementary cycles of the graph with Johnson's algorithm.
+/// Every cycle starts at its smallest node.
+/// In undirected graphs every cycle is only returned in one direction
+/// and an edge together with its reverse is not considered a cycle.
+pub fn cycles<G>(graph: &G) -> Cycles<G>
+where
+    G: Index + IndexAdjacent + Count + Directed,
+{
+    Cycles::new(graph)
+}
+
+pub struct Cycles<G: Base> {
+    node_ids: Vec<Option<NodeId<G::Id>>>,
+    adjacency: Vec<Vec<usize>>,
+    reverse_adjacency: Vec<Vec<usize>>,
+    directed: bool,
+    next_start: usize,
+    start: usize,
+    component: Vec<bool>,
+    blocked: Vec<bool>,
+    blocked_by: Vec<Vec<usize>>,
+    closed: Vec<bool>,
+    path: Vec<usize>,
+    stack: Vec<(usize, usize)>,
+}
+
+impl<G: Base> Cycles<G> {
+    fn new(graph: &G) -> Self
+    where
+        G: Index + IndexAdjacent + Count + Directed,
+    {
+        let count = graph.node_bound();
+        let mut node_ids = vec![None; count];
+        let mut adjacency = vec![Vec::new(); count];
+        let mut reverse_adjacency = vec![Vec::new(); count];
+
+        for from in graph.node_ids() {
+            node_ids[from.as_usize()] = Some(from);
+            for to in graph.adjacent_node_ids(from) {
+                adjacency[from.as_usize()].push(to.as_usize());
+                reverse_adjacency[to.as_usize()].push(from.as_usize());
+            }
+        }
+
+        // parallel edges would yield the same cycle more than once
+        for adj in adjacency.iter_mut().chain(reverse_adjacency.iter_mut()) {
+            adj.sort_unstable();
+            adj.dedup();
+        }
+
+        Self {
+            node_ids,
+            adjacency,
+            reverse_adjacency,
+            directed: G::directed(),
+            next_start: 0,
+            start: 0,
+            component: vec![false; count],
+            blocked: vec![false; count],
+            blocked_by: vec![Vec::new(); count],
+            closed: vec![false; count],
+            path: Vec::new(),
+            stack: Vec::new(),
+        }
+    }
+
+    /// Marks all nodes reachable from start which are not smaller than start.
+    fn reachable(adjacency: &[Vec<usize>], start: usize) -> Vec<bool> {
+        let mut visited = vec![false; adjacency.len()];
+        let mut stack = vec![start];
+        visited[start] = true;
+
+        while let Some(from) = stack.pop() {
+            for &to in &adjacency[from] {
+                if to >= start && !visited[to] {
+                    visited[to] = true;
+                    stack.push(to);
+                }
+            }
+        }
+
+        visited
+    }
+
+    /// Advances to the next start node whose strongly connected component
+    /// in the subgraph of all nodes not smaller than it contains a cycle.
+    fn advance(&mut self) -> bool {
+        while self.next_start < self.adjacency.len() {
+            let start = self.next_start;
+            self.next_start += 1;
+
+            let forward = Self::reachable(&self.adjacency, start);
+            let backward = Self::reachable(&self.reverse_adjacency, start);
+
+            for (node, in_component) in self.component.iter_mut().enumerate() {
+                *in_component = forward[node] && backward[node];
+            }
+
+            if self.adjacency[start].iter().any(|&to| self.component[to]) {
+                self.start = start;
+                self.blocked.fill(false);
+                self.closed.fill(false);
+                for blocked_by in &mut self.blocked_by {
+                    blocked_by.clear();
+                }
+
+                self.blocked[start] = true;
+                self.path.push(start);
+                self.stack.push((start, 0));
+
+                return true;
+            }
+        }
+
+        false
+    }
+
+    fn unblock(&mut self, node: usize) {
+        let mut stack = vec![node];
+
+        while let Some(node) = stack.pop() {
+            if self.blocked[node] {
+                self.blocked[node] = false;
+                stack.append(&mut self.blocked_by[node]);
+            }
+        }
+    }
+
+    fn accept(&self) -> bool {
+        match self.path.len() {
+            _ if self.directed => true,
+            1 => true,
+            2 => false,
+            len => self.path[1] < self.path[len - 1],
+        }
+    }
+}
+
+impl<G: Base> Iterator for Cycles<G> {
+    type Item = Route<G>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.stack.is_empty() && !self.advance() {
+                return None;
+            }
+
+            while let Some((node, index)) = self.stack.last_mut() {
+                let node = *node;
+
+                if let Some(&next) = self.adjacency[node].get(*index) {
+                    *index += 1;
+
+                    if !self.component[next] {
+                        continue;
+                    }
+
+                    if next == self.start {
+                        for &on_path in &self.path {
+                            self.closed[on_path] = true;
+                        }
+
+                        if self.accept() {
+                            let route = self
+                                .path
+                                .iter()
+                                .map(|&node| self.node_ids[node].unwrap())
+                                .collect();
+                            return Some(Route::new(route));
+                        }
+                    } else if !self.blocked[next] {
+                        self.blocked[next] = true;
+                        self.closed[next] = false;
+                        self.path.push(next);
+                        self.stack.push((next, 0));
+                    }
+
+                    continue;
+                }
+
+                if self.closed[node] {
+                    self.unblock(node);
+                } else {
+                    for &next in &self.adjacency[node] {
+                        if self.component[next] && !self.blocked_by[next].contains(&node) {
+                            self.blocked_by[next].push(node);
+                        }
+                    }
+                }
+
+                self.stack.pop();
+                self.path.pop();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{cycles, find_cycle, has_cycle};
+    use crate::{
+        graph::{Base, Contains, Count, Directed, Index, IndexAdjacent},
+        prelude::*,
+        structures::Route,
+        test::{id, weightless_undigraph},
+    };
+
+    fn assert_cycle<G: Contains + Base<Id = usize>>(graph: &G, cycle: &Route<G>) {
+        assert!(cycle.count() > 0);
+
+        for edge_id in cycle.edge_id_cycle() {
+            assert!(graph.contains_edge(edge_id.from(), edge_id.to()).is_some());
+        }
+    }
+
+    fn digraph(edges: &[(usize, usize)], count: usize) -> AdjacencyList<usize, f64, true> {
+        let edges = edges.iter().map(|&(from, to)| (from, to, 1.0));
+        AdjacencyList::try_from(EdgeList::with(edges, count)).unwrap()
+    }
+
+    fn undigraph(edges: &[(usize, usize)], count: usize) -> AdjacencyList<usize, f64> {
+        let edges = edges.iter().map(|&(from, to)| (from, to, 1.0));
+        AdjacencyList::try_from(EdgeList::with(edges, count)).unwrap()
+    }
+
+    fn count_cycles<G: Index + IndexAdjacent + Count + Directed>(graph: &G) -> usize {
+        cycles(graph).count()
+    }
+
+    #[test]
+    fn find_cycle_di_acyclic() {
+        let graph = digraph(&[(0, 1), (1, 2), (0, 2), (2, 3)], 4);
+
+        assert!(!has_cycle(&graph));
+        assert!(find_cycle(&graph).is_none());
+        assert_eq!(count_cycles(&graph), 0);
+    }
+
+    #[test]
+    fn find_cycle_di_witness() {
+        let graph = digraph(&[(3, 0), (0, 1), (1, 2), (2, 0), (2, 4)], 5);
+        let cycle = find_cycle(&graph).unwrap();
+
+        assert_cycle(&graph, &cycle);
+        assert_eq!(cycle.into_raw(), vec![id(0), id(1), id(2)]);
+    }
+
+    #[test]
+    fn find_cycle_di_self_cycle() {
+        let graph = digraph(&[(0, 1), (1, 1)], 2);
+        let cycle = find_cycle(&graph).unwrap();
+
+        assert_eq!(cycle.into_raw(), vec![id(1)]);
+        assert_eq!(count_cycles(&graph), 1);
+    }
+
+    #[test]
+    fn find_cycle_di_complete_3() {
+        let graph = digraph(&[(0, 1), (1, 0), (1, 2), (2, 1), (0, 2), (2, 0)], 3);
+        let all = cycles(&graph).collect::<Vec<_>>();
+
+        for cycle in &all {
+            assert_cycle(&graph, cycle);
+        }
+        // three cycles of length two and two of length three
+        assert_eq!(all.len(), 5);
+    }
+
+    #[test]
+    fn find_cycle_undi_tree() {
+        let graph = undigraph(&[(0, 1), (0, 2), (2, 3), (2, 4)], 5);
+
+        assert!(!has_cycle(&graph));
+        assert_eq!(count_cycles(&graph), 0);
+    }
+
+    #[test]
+    fn find_cycle_undi_complete_4() {
+        let graph = undigraph(&[(0, 1), (0, 2), (0, 3), (1, 2), (1, 3), (2, 3)], 4);
+        let cycle = find_cycle(&graph).unwrap();
+        assert_cycle(&graph, &cycle);
+        assert!(cycle.count() >= 3);
+
+        let all = cycles(&graph).collect::<Vec<_>>();
+        for cycle in &all {
+            assert_cycle(&graph, cycle);
+        }
+        // four triangles and three squares
+        assert_eq!(all.len(), 7);
+    }
+
+    #[test]
+    fn find_cycle_graph1() {
+        let graph: AdjacencyList<_, _> = weightless_undigraph("data/Graph1.txt").unwrap();
+        let cycle = find_cycle(&graph).unwrap();
+
+        assert_cycle(&graph, &cycle);
+        assert!(cycle.count() >= 3);
+    }
+}
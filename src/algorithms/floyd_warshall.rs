@@ -0,0 +1,159 @@
+use crate::{
+    error::{GraphError, GraphResult},
+    graph::{Base, Count, Directed, EdgeCost, Index, Iter, Sortable},
+    prelude::{EdgeRef, NodeId},
+    structures::AllPairs,
+};
+use std::ops::Add;
+
+/// All pairs shortest paths in O(n³), suited for dense graphs.
+/// Negative edge costs are allowed, negative cycles are found on the diagonal.
+pub fn floyd_warshall<N, W, C, G>(graph: &G) -> GraphResult<AllPairs<C, G>>
+where
+    C: Default + Sortable + Copy + Add<C, Output = C>,
+    W: EdgeCost<Cost = C>,
+    G: Count + Directed + Index + Iter + Base<Node = N, Weight = W>,
+{
+    let count = graph.node_bound();
+    let mut all_pairs = AllPairs::with_count(count);
+
+    for from in graph.node_ids() {
+        all_pairs.distances[from.as_usize()][from.as_usize()] = Some(C::default());
+        all_pairs.next[from.as_usize()][from.as_usize()] = Some(from);
+    }
+
+    // parallel edges keep the cheapest cost
+    let mut insert = |from: NodeId<G::Id>, to: NodeId<G::Id>, cost: C| {
+        let distance = &mut all_pairs.distances[from.as_usize()][to.as_usize()];
+        if distance.is_none_or(|distance| cost < distance) {
+            *distance = Some(cost);
+            all_pairs.next[from.as_usize()][to.as_usize()] = Some(to);
+        }
+    };
+
+    for EdgeRef { edge_id, weight } in graph.iter_edges() {
+        insert(edge_id.from(), edge_id.to(), *weight.cost());
+        if !G::directed() {
+            insert(edge_id.to(), edge_id.from(), *weight.cost());
+        }
+    }
+
+    for k in 0..count {
+        let via = all_pairs.distances[k].clone();
+
+        for i in 0..count {
+            let Some(to_k) = all_pairs.distances[i][k] else {
+                continue;
+            };
+
+            for (j, from_k) in via.iter().enumerate() {
+                let Some(from_k) = *from_k else {
+                    continue;
+                };
+
+                let distance = to_k + from_k;
+                if all_pairs.distances[i][j].is_none_or(|current| distance < current) {
+                    all_pairs.distances[i][j] = Some(distance);
+                    all_pairs.next[i][j] = all_pairs.next[i][k];
+                }
+            }
+        }
+    }
+
+    let negative_cycle = all_pairs
+        .distances
+        .iter()
+        .enumerate()
+        .any(|(i, row)| row[i].is_some_and(|distance| distance < C::default()));
+
+    match negative_cycle {
+        true => Err(GraphError::NegativeCycle),
+        false => Ok(all_pairs),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    extern crate test;
+
+    use crate::{
+        algorithms::{_dijkstra, floyd_warshall},
+        error::GraphError,
+        graph::Index,
+        prelude::*,
+        test::{digraph, id, undigraph},
+    };
+    use test::Bencher;
+
+    fn floyd_warshall_matches_johnson<G: Graph<usize, f64>>(graph: &G) {
+        let all_pairs = graph.floyd_warshall().unwrap();
+        let johnson = graph.johnson().unwrap();
+
+        for from in graph.node_ids() {
+            for to in graph.node_ids() {
+                let expected = johnson[from.as_usize()].distance(to);
+                assert_eq!(all_pairs.distance(from, to), expected);
+
+                let Some(route) = all_pairs.path(from, to) else {
+                    assert_eq!(expected, None);
+                    continue;
+                };
+
+                let cost = route
+                    .edge_ids()
+                    .map(|edge_id| graph.weight(edge_id).unwrap())
+                    .sum::<f64>();
+                assert_eq!(Some(&cost), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn floyd_warshall_wege_2_di_adj_list_all_pairs() {
+        let graph: AdjacencyList<_, _, true> = digraph("data/Wege2.txt").unwrap();
+        floyd_warshall_matches_johnson(&graph);
+    }
+
+    #[test]
+    fn floyd_warshall_wege_2_di_adj_mat_all_pairs() {
+        let graph: AdjacencyMatrix<_, _, true> = digraph("data/Wege2.txt").unwrap();
+        floyd_warshall_matches_johnson(&graph);
+    }
+
+    #[test]
+    fn floyd_warshall_wege_3_negative_cycle() {
+        let graph: AdjacencyList<_, _, true> = digraph("data/Wege3.txt").unwrap();
+        assert!(matches!(
+            floyd_warshall(&graph),
+            Err(GraphError::NegativeCycle)
+        ));
+    }
+
+    #[test]
+    fn floyd_warshall_path() {
+        let graph: AdjacencyList<_, _, true> = digraph("data/Wege1.txt").unwrap();
+        let all_pairs = floyd_warshall(&graph).unwrap();
+
+        let route = all_pairs.path(id(2), id(0)).unwrap();
+        assert_eq!(route.first(), Some(id(2)));
+        assert_eq!(route.last(), Some(id(0)));
+        assert_eq!(all_pairs.distance(id(2), id(0)), Some(&6.0));
+        assert_eq!(all_pairs.path(id(1), id(1)).unwrap().count(), 1);
+    }
+
+    #[bench]
+    fn floyd_warshall_k_100_undi_adj_mat(b: &mut Bencher) {
+        let graph: AdjacencyMatrix<_, _> = undigraph("data/K_100.txt").unwrap();
+        let expected = _dijkstra(&graph, id(0), None).unwrap();
+
+        b.iter(|| {
+            let all_pairs = floyd_warshall(&graph).unwrap();
+            for to in graph.node_ids() {
+                assert_eq!(
+                    all_pairs.distance(id(0), to).map(|cost| *cost as f32),
+                    expected.distance(to).map(|cost| *cost as f32)
+                );
+            }
+        })
+    }
+}
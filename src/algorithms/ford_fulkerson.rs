@@ -1,75 +1,160 @@
 use crate::{
-    graph::{Base, Count, EdgeCapacity, EdgeFlow, Get, GetMut, IndexAdjacent},
-    prelude::{EdgeId, NodeId},
-    structures::Parents,
+    graph::{
+        Base, Count, Create, EdgeCapacity, EdgeCost, EdgeFlow, FlowWeight, Get, GetMut, Identifier,
+        Insert, Iter, IterAdjacent,
+    },
+    prelude::{EdgeId, EdgeRef, MultiAdjacencyList, NodeId},
 };
 use std::{
-    fmt::Debug,
-    ops::{AddAssign, Sub, SubAssign},
+    collections::VecDeque,
+    ops::{AddAssign, Neg, Sub, SubAssign},
 };
 
-pub(crate) fn _ford_fulkerson<N, W, C, G, F>(
-    graph: &mut G,
-    source: NodeId<G::Id>,
-    sink: NodeId<G::Id>,
-    mut sp: F,
-) -> C
+/// Residual graph of a flow network.
+/// Every arc is inserted together with its reverse arc, which gets the following key,
+/// so even keys belong to the arcs of the network and odd keys to their reverse arcs.
+pub type ResidualGraph<N, C> = MultiAdjacencyList<N, FlowWeight<C>, true>;
+
+/// The arc paired with `edge_id` in a residual graph.
+pub fn reverse_arc(edge_id: EdgeId<usize>) -> EdgeId<usize> {
+    EdgeId::with_key(
+        edge_id.to(),
+        edge_id.from(),
+        edge_id.key().map(|key| key ^ 1),
+    )
+}
+
+/// Returns true for the reverse arcs of a residual graph.
+pub fn is_reverse_arc<Id: Identifier>(edge_id: EdgeId<Id>) -> bool {
+    edge_id.key().is_some_and(|key| key % 2 == 1)
+}
+
+/// Inserts the arc together with its reverse arc.
+/// The flow of the reverse arc is the residual capacity of the arc.
+pub(crate) fn insert_arc<N, C>(
+    graph: &mut ResidualGraph<N, C>,
+    from: NodeId<usize>,
+    to: NodeId<usize>,
+    weight: FlowWeight<C>,
+) -> EdgeId<usize>
 where
-    F: FnMut(&G, NodeId<G::Id>, NodeId<G::Id>) -> Option<Parents<G>>,
-    C: Default + PartialOrd + Copy + AddAssign + SubAssign + Sub<C, Output = C>,
-    W: EdgeCapacity<Capacity = C> + EdgeFlow<Flow = C>,
-    G: Count + IndexAdjacent + Get + GetMut + Base<Node = N, Weight = W> + Debug,
+    C: Copy + Neg<Output = C> + Sub<C, Output = C>,
 {
-    let mut total_flow = C::default();
+    let FlowWeight {
+        flow,
+        capacity,
+        cost,
+    } = weight;
 
-    // loop while bfs finds a path
-    while let Some(parents) = sp(graph, source, sink) {
-        if parents.is_empty() {
-            break;
-        }
+    let edge_id = graph.insert_edge(from, to, weight);
+    graph.insert_edge(to, from, FlowWeight::new(capacity, -cost, capacity - flow));
+    edge_id
+}
+
+/// Builds the residual graph with a reverse arc for every edge,
+/// so anti-parallel edges keep their own reverse arcs.
+pub(crate) fn _residual_graph<N, W, C, G>(graph: &G) -> ResidualGraph<N, C>
+where
+    N: Clone,
+    C: Copy + Neg<Output = C> + Sub<C, Output = C>,
+    W: EdgeCapacity<Capacity = C> + EdgeCost<Cost = C> + EdgeFlow<Flow = C>,
+    G: Iter + Base<Id = usize, Node = N, Weight = W>,
+{
+    let mut residual_graph = ResidualGraph::with_nodes(graph.iter_nodes().cloned());
 
-        let mut to = sink;
-        let mut bottleneck = None;
+    for EdgeRef { edge_id, weight } in graph.iter_edges() {
+        let weight = FlowWeight::new(*weight.capacity(), *weight.cost(), *weight.flow());
+        insert_arc(&mut residual_graph, edge_id.from(), edge_id.to(), weight);
+    }
 
-        // TODO compute route in parents instead of going over parents manually.
+    residual_graph
+}
 
-        // compute the bottleneck
-        while to != source {
-            let from = unsafe { parents.parent_unchecked(to) };
-            let edge_id = EdgeId::new_unchecked(from, to);
-
-            let weight = graph.weight(edge_id).unwrap();
-            let residual_capacity = *weight.capacity() - *weight.flow();
-
-            bottleneck = match bottleneck {
-                Some(b) => {
-                    if b > residual_capacity {
-                        Some(residual_capacity)
-                    } else {
-                        Some(b)
-                    }
-                }
-                None => Some(residual_capacity),
-            };
+/// Breadth first search for the arcs of a path from `source` to `sink`,
+/// following only the arcs accepted by `admissible`.
+pub(crate) fn augmenting_path<N, C, F>(
+    graph: &ResidualGraph<N, C>,
+    source: NodeId<usize>,
+    sink: NodeId<usize>,
+    mut admissible: F,
+) -> Option<Vec<EdgeId<usize>>>
+where
+    F: FnMut(&FlowWeight<C>) -> bool,
+{
+    let count = graph.node_bound();
+    let mut queue = VecDeque::new();
+    let mut visited = vec![false; count];
+    let mut parents: Vec<Option<EdgeId<usize>>> = vec![None; count];
+
+    queue.push_back(source);
+    visited[source.as_usize()] = true;
+
+    while let Some(from) = queue.pop_front() {
+        if from == sink {
+            let mut path = Vec::new();
+            let mut to = sink;
 
-            to = from;
+            while let Some(edge_id) = parents[to.as_usize()] {
+                path.push(edge_id);
+                to = edge_id.from();
+            }
+
+            path.reverse();
+            return Some(path);
         }
 
-        let bottleneck = bottleneck.unwrap();
-        total_flow += bottleneck;
-        to = sink;
+        for EdgeRef { edge_id, weight } in graph.iter_adjacent_edges(from) {
+            let to = edge_id.to();
+
+            if !visited[to.as_usize()] && admissible(weight) {
+                parents[to.as_usize()] = Some(edge_id);
+                queue.push_back(to);
+                visited[to.as_usize()] = true;
+            }
+        }
+    }
+
+    None
+}
 
-        // assign the bottleneck to every edge in the path
-        while to != source {
-            let from = unsafe { parents.parent_unchecked(to) };
+pub(crate) fn _ford_fulkerson<N, C, F>(
+    graph: &mut ResidualGraph<N, C>,
+    source: NodeId<usize>,
+    sink: NodeId<usize>,
+    mut sp: F,
+) -> C
+where
+    F: FnMut(&ResidualGraph<N, C>, NodeId<usize>, NodeId<usize>) -> Option<Vec<EdgeId<usize>>>,
+    C: Default + PartialOrd + Copy + AddAssign + SubAssign + Sub<C, Output = C>,
+{
+    let mut total_flow = C::default();
 
-            let weight = graph.weight_mut(EdgeId::new_unchecked(from, to)).unwrap();
-            *weight.flow_mut() += bottleneck;
+    // loop while an augmenting path is found
+    while let Some(path) = sp(graph, source, sink) {
+        // compute the bottleneck
+        let bottleneck = path
+            .iter()
+            .map(|&edge_id| {
+                let weight = graph.weight(edge_id).unwrap();
+                *weight.capacity() - *weight.flow()
+            })
+            .reduce(|bottleneck, residual_capacity| {
+                if bottleneck > residual_capacity {
+                    residual_capacity
+                } else {
+                    bottleneck
+                }
+            });
 
-            let weight_rev = graph.weight_mut(EdgeId::new_unchecked(to, from)).unwrap();
-            *weight_rev.flow_mut() -= bottleneck;
+        let Some(bottleneck) = bottleneck else {
+            break;
+        };
+        total_flow += bottleneck;
 
-            to = from;
+        // assign the bottleneck to every arc in the path
+        for edge_id in path {
+            *graph.weight_mut(edge_id).unwrap().flow_mut() += bottleneck;
+            *graph.weight_mut(reverse_arc(edge_id)).unwrap().flow_mut() -= bottleneck;
         }
     }
 
@@ -0,0 +1,177 @@
+use super::{_dijkstra, bellman_ford};
+use crate::{
+    error::{GraphError, GraphResult},
+    graph::{
+        Base, Count, Create, EdgeCost, FlowWeight, Index, IndexAdjacent, Insert, Iter,
+        IterAdjacent, IterMut, Maximum, Sortable,
+    },
+    prelude::{AdjacencyList, EdgeRef, EdgeRefMut, NodeId},
+    structures::Distances,
+};
+use std::ops::{Add, Sub};
+
+/// All pairs shortest paths for graphs with negative edge costs.
+/// The graph is reweighted with the potentials of a bellman ford run from a virtual source,
+/// afterwards dijkstra is run from every node.
+/// The returned distances follow the start nodes in the order of `node_ids`,
+/// so they are indexed by the start node only if the graph has no gaps in its node ids.
+pub fn johnson<N, W, C, G>(graph: &G) -> GraphResult<Vec<Distances<C, G>>>
+where
+    C: Default + Sortable + Maximum + Copy + Add<C, Output = C> + Sub<C, Output = C>,
+    W: EdgeCost<Cost = C>,
+    G: Index
+        + Count
+        + IndexAdjacent
+        + IterAdjacent
+        + Iter
+        + IterMut
+        + Clone
+        + Base<Node = N, Weight = W>,
+{
+    let count = graph.node_bound();
+
+    // the virtual source is connected to every node with cost zero
+    let mut potential_graph =
+        AdjacencyList::<(), FlowWeight<C>, true>::with_nodes(vec![(); count + 1]);
+    let source = NodeId::new_unchecked(count);
+
+    for EdgeRef { edge_id, weight } in graph.iter_edges() {
+        let from = NodeId::new_unchecked(edge_id.from().as_usize());
+        let to = NodeId::new_unchecked(edge_id.to().as_usize());
+        let weight = FlowWeight::new(C::MAX, *weight.cost(), C::default());
+        potential_graph.insert_edge(from, to, weight);
+    }
+
+    for to in 0..count {
+        let weight = FlowWeight::new(C::MAX, C::default(), C::default());
+        potential_graph.insert_edge(source, NodeId::new_unchecked(to), weight);
+    }
+
+    let potentials = match bellman_ford(&potential_graph, source) {
+        Some(distances) => distances.distances,
+        None => return Err(GraphError::NegativeCycle),
+    };
+    let potential = |node_id: NodeId<G::Id>| potentials[node_id.as_usize()].unwrap();
+
+    // every edge cost is non negative after reweighting
+    let mut reweighted = graph.clone();
+    for EdgeRefMut { edge_id, weight } in reweighted.iter_edges_mut() {
+        let cost = *weight.cost() + potential(edge_id.from()) - potential(edge_id.to());
+        *weight.cost_mut() = cost;
+    }
+
+    let all_distances = graph
+        .node_ids()
+        .map(|from| {
+            let mut distances = _dijkstra(&reweighted, from, None).unwrap();
+
+            for (to, distance) in distances.distances.iter_mut().enumerate() {
+                if let Some(distance) = distance {
+                    *distance = *distance - potential(from) + potentials[to].unwrap();
+                }
+            }
+
+            distances
+        })
+        .collect();
+
+    Ok(all_distances)
+}
+
+#[cfg(test)]
+mod test {
+    extern crate test;
+
+    use crate::{
+        algorithms::bellman_ford,
+        error::GraphError,
+        prelude::*,
+        test::{digraph, id},
+    };
+    use test::Bencher;
+
+    fn johnson_matches_bellman_ford<G: Graph<usize, f64>>(graph: &G) {
+        let all_distances = graph.johnson().unwrap();
+
+        for from in graph.node_ids() {
+            let expected = bellman_ford(graph, from).unwrap();
+            assert_eq!(all_distances[from.as_usize()].distances, expected.distances);
+        }
+    }
+
+    #[test]
+    fn johnson_wege_2_di_adj_list_all_pairs() {
+        let graph: AdjacencyList<_, _, true> = digraph("data/Wege2.txt").unwrap();
+        johnson_matches_bellman_ford(&graph);
+    }
+
+    #[test]
+    fn johnson_wege_2_di_adj_mat_all_pairs() {
+        let graph: AdjacencyMatrix<_, _, true> = digraph("data/Wege2.txt").unwrap();
+        johnson_matches_bellman_ford(&graph);
+    }
+
+    #[bench]
+    fn johnson_wege_1_di_adj_list(b: &mut Bencher) {
+        let graph: AdjacencyList<_, _, true> = digraph("data/Wege1.txt").unwrap();
+
+        b.iter(|| {
+            let all_distances = graph.johnson().unwrap();
+            let total = *all_distances[2].distance(id(0)).unwrap();
+            assert_eq!(total as f32, 6.0)
+        })
+    }
+
+    #[bench]
+    fn johnson_wege_2_di_adj_list(b: &mut Bencher) {
+        let graph: AdjacencyList<_, _, true> = digraph("data/Wege2.txt").unwrap();
+
+        b.iter(|| {
+            let all_distances = graph.johnson().unwrap();
+            let total = *all_distances[2].distance(id(0)).unwrap();
+            assert_eq!(total as f32, 2.0)
+        })
+    }
+
+    #[bench]
+    fn johnson_wege_3_di_adj_list(b: &mut Bencher) {
+        let graph: AdjacencyList<_, _, true> = digraph("data/Wege3.txt").unwrap();
+
+        b.iter(|| {
+            let result = graph.johnson();
+            assert!(matches!(result, Err(GraphError::NegativeCycle)))
+        })
+    }
+
+    #[bench]
+    fn johnson_wege_1_di_adj_mat(b: &mut Bencher) {
+        let graph: AdjacencyMatrix<_, _, true> = digraph("data/Wege1.txt").unwrap();
+
+        b.iter(|| {
+            let all_distances = graph.johnson().unwrap();
+            let total = *all_distances[2].distance(id(0)).unwrap();
+            assert_eq!(total as f32, 6.0)
+        })
+    }
+
+    #[bench]
+    fn johnson_wege_2_di_adj_mat(b: &mut Bencher) {
+        let graph: AdjacencyMatrix<_, _, true> = digraph("data/Wege2.txt").unwrap();
+
+        b.iter(|| {
+            let all_distances = graph.johnson().unwrap();
+            let total = *all_distances[2].distance(id(0)).unwrap();
+            assert_eq!(total as f32, 2.0)
+        })
+    }
+
+    #[bench]
+    fn johnson_wege_3_di_adj_mat(b: &mut Bencher) {
+        let graph: AdjacencyMatrix<_, _, true> = digraph("data/Wege3.txt").unwrap();
+
+        b.iter(|| {
+            let result = graph.johnson();
+            assert!(matches!(result, Err(GraphError::NegativeCycle)))
+        })
+    }
+}
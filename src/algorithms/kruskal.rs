@@ -12,7 +12,7 @@ where
     let mut priority_queue = graph.iter_edges().collect::<Vec<_>>();
     priority_queue.sort_by(|this, other| this.weight.cost().sort(other.weight.cost()));
 
-    let count = graph.node_count();
+    let count = graph.node_bound();
     let mut tree = TreeBuilder::with_count(count);
     let mut union_find = UnionFind::<G>::with_node_ids(graph.node_ids());
 
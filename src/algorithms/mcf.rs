@@ -1,51 +1,41 @@
 use crate::{
-    algorithms::_edmonds_karp,
+    algorithms::{_edmonds_karp, _residual_graph, insert_arc, ResidualGraph},
     graph::{
-        Base, Count, EdgeCapacity, EdgeCost, EdgeDirection, EdgeFlow, Get, GetMut, Index,
-        IndexAdjacent, Insert, Iter, NodeBalance, Remove,
+        Base, EdgeCapacity, EdgeCost, EdgeFlow, FlowWeight, Get, Index, Insert, Iter, NodeBalance,
+        Remove,
     },
-    prelude::{EdgeRef, NodeId},
+    prelude::NodeId,
 };
 use std::{
     fmt::Debug,
     ops::{AddAssign, Neg, Sub, SubAssign},
 };
 
-pub struct Mcf<G: Base> {
-    pub residual_graph: G,
-    pub source: NodeId<G::Id>,
-    pub sink: NodeId<G::Id>,
+pub struct Mcf<N, C> {
+    pub residual_graph: ResidualGraph<N, C>,
+    pub source: NodeId<usize>,
+    pub sink: NodeId<usize>,
 }
 
-impl<G: Base> Mcf<G> {
-    pub fn init<N, W, C>(graph: &G) -> Self
+impl<N, C> Mcf<N, C>
+where
+    N: Default + NodeBalance<Balance = C>,
+    C: Default
+        + PartialOrd
+        + Copy
+        + Neg<Output = C>
+        + AddAssign
+        + SubAssign
+        + Debug
+        + Sub<C, Output = C>,
+{
+    pub fn init<W, G>(graph: &G) -> Self
     where
-        N: Default + NodeBalance<Balance = C>,
-        W: EdgeCapacity<Capacity = C>
-            + EdgeCost<Cost = C>
-            + Default
-            + EdgeDirection
-            + EdgeFlow<Flow = C>,
-        C: Default
-            + PartialOrd
-            + Copy
-            + Neg<Output = C>
-            + AddAssign
-            + SubAssign
-            + Debug
-            + Sub<C, Output = C>,
-        G: Index
-            + Get
-            + GetMut
-            + Insert
-            + Remove
-            + Count
-            + IndexAdjacent
-            + Iter
-            + Clone
-            + Base<Node = N, Weight = W>,
+        N: Clone,
+        W: EdgeCapacity<Capacity = C> + EdgeCost<Cost = C> + EdgeFlow<Flow = C>,
+        G: Index + Iter + Base<Id = usize, Node = N, Weight = W>,
     {
-        let mut residual_graph = graph.clone();
+        let mut residual_graph = _residual_graph(graph);
         let source = residual_graph.insert_node(N::default());
         let sink = residual_graph.insert_node(N::default());
 
@@ -53,38 +43,14 @@ impl<G: Base> Mcf<G> {
             let node = residual_graph.node(node_id).unwrap();
             let balance = *node.balance();
 
-            let mut weight = W::default();
-            let mut rev_weight = W::default();
-            rev_weight.reverse();
-
             if balance > C::default() {
                 // supply
-                *weight.capacity_mut() = balance;
-                *rev_weight.capacity_mut() = balance;
-                *rev_weight.flow_mut() = balance;
-
-                residual_graph.insert_edge(source, node_id, weight);
-                residual_graph.insert_edge(node_id, source, rev_weight);
-            } else if node.balance() < &C::default() {
+                let weight = FlowWeight::new(balance, C::default(), C::default());
+                insert_arc(&mut residual_graph, source, node_id, weight);
+            } else if balance < C::default() {
                 // demand
-                *weight.capacity_mut() = -balance;
-                *rev_weight.capacity_mut() = -balance;
-                *rev_weight.flow_mut() = -balance;
-
-                residual_graph.insert_edge(node_id, sink, weight);
-                residual_graph.insert_edge(sink, node_id, rev_weight);
-            }
-        }
-
-        for EdgeRef { edge_id, weight } in graph.iter_edges() {
-            if !residual_graph.contains_edge_id(edge_id.rev()) {
-                let mut w = W::default();
-                *w.cost_mut() = -*weight.cost();
-                *w.capacity_mut() = *weight.capacity();
-                *w.flow_mut() = *weight.capacity() - *weight.flow();
-                w.reverse();
-
-                residual_graph.insert_edge(edge_id.to(), edge_id.from(), w);
+                let weight = FlowWeight::new(-balance, C::default(), C::default());
+                insert_arc(&mut residual_graph, node_id, sink, weight);
             }
         }
 
@@ -95,30 +61,7 @@ impl<G: Base> Mcf<G> {
         }
     }
 
-    pub fn solvable<N, W, C>(&mut self) -> bool
-    where
-        N: Default + NodeBalance<Balance = C>,
-        C: Default
-            + PartialOrd
-            + Copy
-            + Neg<Output = C>
-            + AddAssign
-            + SubAssign
-            + Debug
-            + Sub<C, Output = C>,
-        W: EdgeCapacity<Capacity = C> + EdgeFlow<Flow = C>,
-        G: Index
-            + Get
-            + GetMut
-            + Insert
-            + Remove
-            + Count
-            + IndexAdjacent
-            + Iter
-            + Clone
-            + Base<Node = N, Weight = W>
-            + Debug,
-    {
+    pub fn solvable(&mut self) -> bool {
         let total_flow = _edmonds_karp(&mut self.residual_graph, self.source, self.sink);
         let expected = self
             .residual_graph
@@ -130,8 +73,9 @@ impl<G: Base> Mcf<G> {
                 akku
             });
 
-        self.residual_graph.remove_node(self.source);
+        // the sink was inserted last, so removing it first keeps the id of the source valid
         self.residual_graph.remove_node(self.sink);
+        self.residual_graph.remove_node(self.source);
 
         total_flow == expected
     }
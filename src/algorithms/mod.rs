@@ -1,5 +1,7 @@
+pub use astar::*;
 pub use bellman_ford::*;
 pub use bfs::*;
+pub use bidirectional_dijkstra::*;
 pub use branch_bound::*;
 pub use brute_force::*;
 pub use capacity_scaling::*;
@@ -8,6 +10,8 @@ pub use dfs::*;
 pub use dijkstra::*;
 pub use double_tree::*;
 pub use edmonds_karp::*;
+pub use find_cycle::*;
+pub use floyd_warshall::*;
 pub use ford_fulkerson::*;
 pub use johnson::*;
 pub use kruskal::*;
@@ -16,9 +20,12 @@ pub use nearest_neighbor::*;
 pub use prim::*;
 pub use ssp::*;
 pub use union_find::*;
+pub use yen::*;
 
+mod astar;
 mod bellman_ford;
 mod bfs;
+mod bidirectional_dijkstra;
 mod branch_bound;
 mod brute_force;
 mod capacity_scaling;
@@ -27,6 +34,8 @@ mod dfs;
 mod dijkstra;
 mod double_tree;
 mod edmonds_karp;
+mod find_cycle;
+mod floyd_warshall;
 mod ford_fulkerson;
 mod johnson;
 mod kruskal;
@@ -35,3 +44,4 @@ mod nearest_neighbor;
 mod prim;
 mod ssp;
 mod union_find;
+mod yen;
@@ -32,7 +32,7 @@ where
         Diverged,
     }
 
-    let mut states = vec![Status::default(); graph.node_count()];
+    let mut states = vec![Status::default(); graph.node_bound()];
     let mut path = vec![(start, C::default())];
     let mut prev = start;
 
@@ -23,7 +23,7 @@ where
     W: EdgeCost<Cost = C>,
     G: IndexAdjacent + Count + IterAdjacent + Base<Node = N, Weight = W>,
 {
-    let n = graph.node_count();
+    let n = graph.node_bound();
     let mut visited = vec![false; n];
     let mut priority_queue = PriorityQueue::with_capacity(n);
     // einfach mit W::max init
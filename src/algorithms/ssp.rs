@@ -7,12 +7,12 @@ use std::{
 use num_traits::{Float, Pow};
 
 use crate::{
-    algorithms::{Mcf, _ford_fulkerson, bfs_sp},
+    algorithms::{Mcf, _ford_fulkerson, _residual_graph, bfs_sp, is_reverse_arc},
     graph::{
-        Base, Count, EdgeCapacity, EdgeCost, EdgeDirection, EdgeFlow, Get, GetMut, Index,
-        IndexAdjacent, Insert, Iter, IterAdjacent, IterMut, NodeBalance, Remove, Sortable,
+        Base, Count, EdgeCapacity, EdgeCost, EdgeFlow, Get, GetMut, Index, IndexAdjacent, Insert,
+        Iter, IterAdjacent, IterMut, NodeBalance, Remove, Sortable,
     },
-    prelude::{EdgeRef, NodeId},
+    prelude::NodeId,
     structures::Parents,
 };
 
@@ -21,12 +21,8 @@ use super::{bellman_ford, dijkstra};
 /// successive shortest path
 pub fn ssp<N, W, C, G>(graph: &G) -> Option<C>
 where
-    N: Default + NodeBalance<Balance = C>,
-    W: EdgeCapacity<Capacity = C>
-        + EdgeCost<Cost = C>
-        + Default
-        + EdgeDirection
-        + EdgeFlow<Flow = C>,
+    N: Default + Clone + NodeBalance<Balance = C>,
+    W: EdgeCapacity<Capacity = C> + EdgeCost<Cost = C> + Default + EdgeFlow<Flow = C>,
     C: Default
         + PartialOrd
         + Copy
@@ -49,7 +45,7 @@ where
         + Iter
         + IterMut
         + Clone
-        + Base<Node = N, Weight = W>
+        + Base<Id = usize, Node = N, Weight = W>
         + Debug,
 {
     // let Mcf {
@@ -69,32 +65,24 @@ where
         None => return None,
     };
 
-    let mut graphR = graph.clone();
+    let mut residual_graph = _residual_graph(graph);
 
-    for edge in graphR.iter_edges_mut() {
-        *edge.weight.flow_mut() += delta;
-    }
-
-    for EdgeRef { edge_id, weight } in graph.iter_edges() {
-        if !graphR.contains_edge_id(edge_id.rev()) {
-            let mut w = W::default();
-            *w.cost_mut() = -*weight.cost();
-            *w.capacity_mut() = *weight.capacity();
-            *w.flow_mut() = *weight.capacity() - *weight.flow();
-            w.reverse();
-
-            graphR.insert_edge(edge_id.to(), edge_id.from(), w);
+    for edge in residual_graph.iter_edges_mut() {
+        if !is_reverse_arc(edge.edge_id) {
+            *edge.weight.flow_mut() += delta;
         }
     }
 
-    let cost = graphR.iter_edges().fold(C::default(), |mut akku, edge| {
-        let weight = edge.weight;
-        if !weight.is_reverse() {
-            akku += *weight.flow() * *weight.cost();
-        }
-
-        akku
-    });
+    let cost = residual_graph
+        .iter_edges()
+        .fold(C::default(), |mut akku, edge| {
+            let weight = edge.weight;
+            if !is_reverse_arc(edge.edge_id) {
+                akku += *weight.flow() * *weight.cost();
+            }
+
+            akku
+        });
 
     Some(cost)
 }
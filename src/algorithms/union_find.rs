@@ -5,6 +5,8 @@ use std::ops::{Deref, DerefMut};
 pub struct UnionFind<G: Base> {
     parents: Parents<G>,
     rank: Vec<u32>,
+    /// The root of the set grown by the last union, or the first node
+    root: Option<NodeId<G::Id>>,
 }
 
 impl<G: Base> Deref for UnionFind<G> {
@@ -24,9 +26,21 @@ impl<G: Base> DerefMut for UnionFind<G> {
 impl<G: Base> From<Parents<G>> for UnionFind<G> {
     fn from(parents: Parents<G>) -> Self {
         let count = parents.count();
+        // the root of the first node with a parent
+        let root = (0..count)
+            .find_map(|index| parents.parent_at(index))
+            .map(|mut node| {
+                while let Some(parent) = parents.parent(node)
+                    && parent != node
+                {
+                    node = parent;
+                }
+                node
+            });
         Self {
             parents,
             rank: vec![1; count],
+            root,
         }
     }
 }
@@ -36,28 +50,34 @@ impl<G: Base> UnionFind<G> {
         Self {
             parents: Parents::with_count(count),
             rank: vec![1; count],
+            root: None,
         }
     }
 
     pub fn with_node_ids(node_ids: impl IntoIterator<Item = NodeId<G::Id>>) -> Self {
-        let parents = node_ids
-            .into_iter()
-            .map(|node_id| Some(node_id))
-            .collect::<Vec<_>>();
-        let count = parents.len();
+        let node_ids = node_ids.into_iter().collect::<Vec<_>>();
+        // node ids of stable graphs may have gaps
+        let count = node_ids
+            .iter()
+            .map(|node_id| node_id.as_usize() + 1)
+            .max()
+            .unwrap_or(0);
+
+        let mut parents = Parents::with_count(count);
+        for &node_id in &node_ids {
+            parents.insert(node_id, node_id);
+        }
 
         Self {
-            parents: Parents::new(parents),
+            parents,
             rank: vec![1; count],
+            root: node_ids.first().copied(),
         }
     }
 
-    pub fn root(&self) -> NodeId<G::Id> {
-        // self.find(0.into())
-        unsafe {
-            self.parents
-                .parent_unchecked(NodeId::new_unchecked(0.into()))
-        }
+    /// The root of the set grown by the last union, the first node before any union
+    pub fn root(&self) -> Option<NodeId<G::Id>> {
+        self.root
     }
 
     pub fn rank(&self, index: NodeId<G::Id>) -> u32 {
@@ -98,6 +118,7 @@ impl<G: Base> UnionFind<G> {
         }
         self.insert(root_x, root_y);
         self.rank[root_x.as_usize()] += self.rank[root_y.as_usize()];
+        self.root = Some(root_x);
         root_x
     }
 }
@@ -118,6 +139,7 @@ mod tests {
 
         // Initially, each element is in its own set
         assert_eq!(union_find.count(), count);
+        assert_eq!(union_find.root(), Some(id(0)));
         for i in 0..count {
             let i = NodeId::new_unchecked(i);
             assert_eq!(union_find.find(i), i);
@@ -142,7 +164,9 @@ mod tests {
         assert_eq!(union_find.rank(id(4)), 1);
 
         assert_eq!(union_find.union(id(3), id(5)), id(2));
+        assert_eq!(union_find.root(), Some(id(2)));
         assert_eq!(union_find.union(id(6), id(0)), id(0));
+        assert_eq!(union_find.root(), Some(id(0)));
 
         assert_eq!(union_find.find(id(0)), id(0));
         assert_eq!(union_find.rank(id(0)), 3);
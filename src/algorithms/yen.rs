@@ -0,0 +1,248 @@
+use super::{_dijkstra, _dijkstra_filtered};
+use crate::{
+    graph::{Base, Count, EdgeCost, IndexAdjacent, IterAdjacent, Sortable},
+    prelude::NodeId,
+    structures::{Distances, Route},
+};
+use priq::PriorityQueue;
+use std::{collections::HashSet, ops::Add};
+
+/// The nodes of a path, each with the cost to reach it from the start of the path
+type Path<C, Id> = Vec<(NodeId<Id>, C)>;
+
+/// The simple paths between two nodes in the order of their cost, see [yen]
+pub struct Yen<'a, C, G>
+where
+    C: PartialOrd,
+    G: Base,
+{
+    graph: &'a G,
+    from: NodeId<G::Id>,
+    to: NodeId<G::Id>,
+    shortest: Vec<Path<C, G::Id>>,
+    candidates: PriorityQueue<C, Path<C, G::Id>>,
+    seen: HashSet<Vec<NodeId<G::Id>>>,
+}
+
+/// Yen's k shortest loopless paths from `from` to `to`, use `take(k)` to limit them.
+/// Every next path branches off a previous one at a spur node,
+/// the spur path is found by dijkstra without the nodes before the spur node
+/// and without the edges leaving it on previous paths with the same root.
+pub fn yen<N, W, C, G>(graph: &G, from: NodeId<G::Id>, to: NodeId<G::Id>) -> Yen<'_, C, G>
+where
+    C: Default + Sortable + Copy + Add<C, Output = C>,
+    W: EdgeCost<Cost = C>,
+    G: IndexAdjacent + Count + IterAdjacent + Base<Node = N, Weight = W>,
+{
+    Yen {
+        graph,
+        from,
+        to,
+        shortest: Vec::new(),
+        candidates: PriorityQueue::new(),
+        seen: HashSet::new(),
+    }
+}
+
+impl<N, W, C, G> Yen<'_, C, G>
+where
+    C: Default + Sortable + Copy + Add<C, Output = C>,
+    W: EdgeCost<Cost = C>,
+    G: IndexAdjacent + Count + IterAdjacent + Base<Node = N, Weight = W>,
+{
+    fn path(distances: &Distances<C, G>, from: NodeId<G::Id>, to: NodeId<G::Id>) -> Path<C, G::Id> {
+        let mut path = vec![(to, *distances.distance(to).unwrap())];
+        let mut node = to;
+
+        while node != from {
+            node = distances.parents.parent(node).unwrap();
+            path.push((node, *distances.distance(node).unwrap()));
+        }
+
+        path.reverse();
+        path
+    }
+
+    /// Adds the deviations of the last shortest path to the candidates
+    fn spurs(&mut self) {
+        let last = self.shortest.last().unwrap().clone();
+        let mut removed = vec![false; self.graph.node_bound()];
+
+        for (i, &(spur, root_cost)) in last.iter().enumerate().take(last.len() - 1) {
+            let root = &last[..=i];
+            let excluded = self
+                .shortest
+                .iter()
+                .filter(|path| {
+                    path.len() > i + 1
+                        && path[..=i]
+                            .iter()
+                            .zip(root)
+                            .all(|((node, _), (other, _))| node == other)
+                })
+                .map(|path| (spur, path[i + 1].0))
+                .collect::<Vec<_>>();
+
+            let spur_distances = _dijkstra_filtered(self.graph, spur, Some(self.to), |edge_id| {
+                !removed[edge_id.to().as_usize()]
+                    && !excluded.contains(&(edge_id.from(), edge_id.to()))
+            });
+
+            // the root must stay loopless, so the following spur paths avoid this node
+            removed[spur.as_usize()] = true;
+
+            let Some(spur_distances) = spur_distances else {
+                continue;
+            };
+
+            let mut candidate = last[..i].to_vec();
+            candidate.extend(
+                Self::path(&spur_distances, spur, self.to)
+                    .into_iter()
+                    .map(|(node, cost)| (node, root_cost + cost)),
+            );
+
+            let nodes = candidate.iter().map(|(node, _)| *node).collect();
+            if self.seen.insert(nodes) {
+                let (_, cost) = *candidate.last().unwrap();
+                self.candidates.put(cost, candidate);
+            }
+        }
+    }
+}
+
+impl<N, W, C, G> Iterator for Yen<'_, C, G>
+where
+    C: Default + Sortable + Copy + Add<C, Output = C>,
+    W: EdgeCost<Cost = C>,
+    G: IndexAdjacent + Count + IterAdjacent + Base<Node = N, Weight = W>,
+{
+    type Item = (Route<G>, C);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let path = if self.shortest.is_empty() {
+            let distances = _dijkstra(self.graph, self.from, Some(self.to))?;
+            let path = Self::path(&distances, self.from, self.to);
+            let nodes = path.iter().map(|(node, _)| *node).collect();
+            self.seen.insert(nodes);
+            path
+        } else {
+            self.spurs();
+            self.candidates.pop()?.1
+        };
+
+        let (_, cost) = *path.last().unwrap();
+        let route = Route::new(path.iter().map(|(node, _)| *node).collect());
+        self.shortest.push(path);
+
+        Some((route, cost))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    extern crate test;
+
+    use crate::{
+        algorithms::{dijkstra_between, yen},
+        graph::{Base, Create, Get, Insert, IterAdjacent},
+        prelude::*,
+        test::{digraph, id, undigraph},
+    };
+    use test::Bencher;
+
+    /// The costs of all simple paths from `node` to `to` by depth first search
+    fn simple_path_costs<G>(
+        graph: &G,
+        path: &mut Vec<NodeId<usize>>,
+        to: NodeId<usize>,
+        costs: &mut Vec<f64>,
+    ) where
+        G: IterAdjacent + Base<Id = usize, Weight = f64>,
+    {
+        let node = *path.last().unwrap();
+        if node == to {
+            let cost = path
+                .array_windows::<2>()
+                .map(|[from, to]| {
+                    graph
+                        .iter_adjacent_edges(*from)
+                        .find(|edge| edge.edge_id.to() == *to)
+                        .map(|edge| *edge.weight)
+                        .unwrap()
+                })
+                .sum();
+            costs.push(cost);
+            return;
+        }
+
+        let adjacent = graph
+            .iter_adjacent_edges(node)
+            .map(|edge| edge.edge_id.to())
+            .collect::<Vec<_>>();
+
+        for next in adjacent {
+            if !path.contains(&next) {
+                path.push(next);
+                simple_path_costs(graph, path, to, costs);
+                path.pop();
+            }
+        }
+    }
+
+    #[test]
+    fn yen_wege_1_all_simple_paths() {
+        let graph: AdjacencyList<_, _, true> = digraph("data/Wege1.txt").unwrap();
+
+        let mut expected = Vec::new();
+        simple_path_costs(&graph, &mut vec![id(2)], id(0), &mut expected);
+        expected.sort_by(f64::total_cmp);
+
+        let paths = yen(&graph, id(2), id(0)).collect::<Vec<_>>();
+        let costs = paths.iter().map(|(_, cost)| *cost).collect::<Vec<_>>();
+        assert_eq!(costs, expected);
+
+        for (route, cost) in paths {
+            let nodes = route.node_ids();
+            assert_eq!(nodes.first(), Some(&id(2)));
+            assert_eq!(nodes.last(), Some(&id(0)));
+            assert!((1..nodes.len()).all(|i| !nodes[..i].contains(&nodes[i])));
+
+            let sum = route
+                .edge_ids()
+                .map(|edge_id| graph.weight(edge_id).unwrap())
+                .sum::<f64>();
+            assert_eq!(sum, cost);
+        }
+    }
+
+    #[test]
+    fn yen_g_1_2_non_decreasing() {
+        let graph: AdjacencyList<_, _> = undigraph("data/G_1_2.txt").unwrap();
+        let paths = yen(&graph, id(0), id(1)).take(10).collect::<Vec<_>>();
+
+        assert_eq!(paths.len(), 10);
+        assert_eq!(Some(paths[0].1), dijkstra_between(&graph, id(0), id(1)));
+        assert!(paths.array_windows::<2>().all(|[(_, a), (_, b)]| a <= b));
+    }
+
+    #[test]
+    fn yen_unreachable() {
+        let graph: AdjacencyList<_, _, true> = digraph("data/Wege1.txt").unwrap();
+        assert_eq!(yen(&graph, id(2), id(2)).count(), 1);
+
+        let mut graph = AdjacencyList::<usize, f64, true>::with_nodes(0..2);
+        graph.insert_edge(id(0), id(1), 1.0);
+        assert_eq!(yen(&graph, id(1), id(0)).count(), 0);
+    }
+
+    #[bench]
+    fn yen_g_1_2_undi_adj_list(b: &mut Bencher) {
+        let graph: AdjacencyList<_, _> = undigraph("data/G_1_2.txt").unwrap();
+
+        b.iter(|| {
+            let (_, cost) = yen(&graph, id(0), id(1)).nth(4).unwrap();
+            assert!(cost as f32 >= 2.36802)
+        })
+    }
+}
@@ -0,0 +1,400 @@
+use crate::{
+    algorithms::is_reverse_arc,
+    edge_list::{EdgeList, Tokens},
+    error::{GraphError, GraphResult, ParseErrorKind},
+    graph::{BalancedNode, Base, FlowWeight, Iter},
+    structures::SparseMatrixBuilder,
+};
+use std::{
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Problem {
+    /// Minimal cost flow, the nodes carry supplies and demands
+    Min,
+    /// Maximal flow from the source to the sink
+    Max,
+}
+
+/// A flow instance in the DIMACS format.
+/// The node ids of the file start at one, here they are shifted to start at zero.
+/// Max flow arcs have no cost and parallel arcs are kept.
+/// The lower bounds of min cost arcs are shipped in advance,
+/// which moves them from the capacities into the balances and the cost offset.
+/// Arcs are written with a lower bound of 0, the cost offset is not written.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Dimacs {
+    pub problem: Problem,
+    pub edge_list: EdgeList<BalancedNode<usize, f64>, FlowWeight<f64>, true>,
+    pub source: Option<usize>,
+    pub sink: Option<usize>,
+    /// The cost of the lower bounds, to be added to the cost of a flow in the edge list
+    pub cost_offset: f64,
+}
+
+impl Dimacs {
+    pub fn min(edge_list: EdgeList<BalancedNode<usize, f64>, FlowWeight<f64>, true>) -> Self {
+        Self {
+            problem: Problem::Min,
+            edge_list,
+            source: None,
+            sink: None,
+            cost_offset: 0.0,
+        }
+    }
+
+    pub fn max(
+        edge_list: EdgeList<BalancedNode<usize, f64>, FlowWeight<f64>, true>,
+        source: usize,
+        sink: usize,
+    ) -> Self {
+        Self {
+            problem: Problem::Max,
+            edge_list,
+            source: Some(source),
+            sink: Some(sink),
+            cost_offset: 0.0,
+        }
+    }
+}
+
+/// Parses a node id starting at one
+fn parse_node(tokens: &mut Tokens, node_count: usize) -> GraphResult<usize> {
+    let token = tokens.token()?;
+    let node = tokens.value::<usize>(token)?;
+
+    if node == 0 || node > node_count {
+        return Err(tokens.error(Some(token), ParseErrorKind::NodeOutOfRange { node_count }));
+    }
+
+    Ok(node - 1)
+}
+
+impl FromStr for Dimacs {
+    type Err = GraphError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = Tokens::lines(s)
+            .filter(|tokens| !matches!(tokens.text.split_whitespace().next(), None | Some("c")));
+
+        let mut header = lines.next().unwrap_or_else(|| Tokens::new(1, ""));
+        let designator = header.token()?;
+        if designator != "p" {
+            let kind = ParseErrorKind::Expected("the problem line `p min` or `p max`");
+            return Err(header.error(Some(designator), kind));
+        }
+
+        let problem = match header.token()? {
+            "min" => Problem::Min,
+            "max" => Problem::Max,
+            token => {
+                let kind = ParseErrorKind::Expected("`min` or `max`");
+                return Err(header.error(Some(token), kind));
+            }
+        };
+        let node_count = header.parse::<usize>()?;
+        let arc_count = header.parse::<usize>()?;
+
+        let mut nodes = (0..node_count)
+            .map(|node_id| BalancedNode::new(node_id, 0.0))
+            .collect::<Vec<_>>();
+        let mut edges = SparseMatrixBuilder::with_capacity(node_count, node_count);
+        let mut lower_bounds = vec![0.0; node_count];
+        let mut cost_offset = 0.0;
+        let mut found = 0;
+        let mut source = None;
+        let mut sink = None;
+
+        for mut tokens in lines {
+            let designator = tokens.token()?;
+
+            match (designator, problem) {
+                ("n", Problem::Min) => {
+                    let node = parse_node(&mut tokens, node_count)?;
+                    nodes[node].balance = tokens.parse::<f64>()?;
+                }
+                ("n", Problem::Max) => {
+                    let node = parse_node(&mut tokens, node_count)?;
+                    match tokens.token()? {
+                        "s" => source = Some(node),
+                        "t" => sink = Some(node),
+                        token => {
+                            let kind = ParseErrorKind::Expected("`s` or `t`");
+                            return Err(tokens.error(Some(token), kind));
+                        }
+                    }
+                }
+                ("a", Problem::Min) => {
+                    let from = parse_node(&mut tokens, node_count)?;
+                    let to = parse_node(&mut tokens, node_count)?;
+                    let lower = tokens.parse::<f64>()?;
+
+                    let token = tokens.token()?;
+                    let capacity = tokens.value::<f64>(token)?;
+                    if capacity < lower {
+                        let kind =
+                            ParseErrorKind::Expected("a capacity of at least the lower bound");
+                        return Err(tokens.error(Some(token), kind));
+                    }
+
+                    let cost = tokens.parse::<f64>()?;
+
+                    // the flow weights have no lower bound, so it is shipped in advance
+                    lower_bounds[from] -= lower;
+                    lower_bounds[to] += lower;
+                    cost_offset += lower * cost;
+
+                    edges.insert(from, to, FlowWeight::new(capacity - lower, cost, 0.0));
+                    found += 1;
+                }
+                ("a", Problem::Max) => {
+                    let from = parse_node(&mut tokens, node_count)?;
+                    let to = parse_node(&mut tokens, node_count)?;
+                    let capacity = tokens.parse::<f64>()?;
+                    edges.insert(from, to, FlowWeight::new(capacity, 0.0, 0.0));
+                    found += 1;
+                }
+                (token, _) => {
+                    let kind = ParseErrorKind::Expected("a `c`, `n` or `a` line");
+                    return Err(tokens.error(Some(token), kind));
+                }
+            }
+        }
+
+        if found != arc_count {
+            let kind = ParseErrorKind::ArcCount {
+                expected: arc_count,
+                found,
+            };
+            return Err(header.error(Some(designator), kind));
+        }
+
+        if problem == Problem::Max && (source.is_none() || sink.is_none()) {
+            let kind = ParseErrorKind::Expected("a source and a sink");
+            return Err(header.error(Some(designator), kind));
+        }
+
+        // the balances may follow the arcs
+        for (node, lower_bound) in nodes.iter_mut().zip(lower_bounds) {
+            node.balance += lower_bound;
+        }
+
+        Ok(Self {
+            problem,
+            edge_list: EdgeList {
+                nodes,
+                edges: edges.build_all(),
+                node_count,
+            },
+            source,
+            sink,
+            cost_offset,
+        })
+    }
+}
+
+impl Display for Dimacs {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let EdgeList {
+            nodes,
+            edges,
+            node_count,
+        } = &self.edge_list;
+
+        match self.problem {
+            Problem::Min => {
+                writeln!(f, "p min {node_count} {}", edges.nnz())?;
+
+                for (node_id, node) in nodes.iter().enumerate() {
+                    if node.balance != 0.0 {
+                        writeln!(f, "n {} {}", node_id + 1, node.balance)?;
+                    }
+                }
+
+                for (from, to, weight) in edges.iter() {
+                    let (capacity, cost) = (weight.capacity, weight.cost);
+                    writeln!(f, "a {} {} 0 {capacity} {cost}", from + 1, to + 1)?;
+                }
+            }
+            Problem::Max => {
+                writeln!(f, "p max {node_count} {}", edges.nnz())?;
+
+                if let Some(source) = self.source {
+                    writeln!(f, "n {} s", source + 1)?;
+                }
+
+                if let Some(sink) = self.sink {
+                    writeln!(f, "n {} t", sink + 1)?;
+                }
+
+                for (from, to, weight) in edges.iter() {
+                    writeln!(f, "a {} {} {}", from + 1, to + 1, weight.capacity)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// A flow in the DIMACS solution format.
+/// Writes the objective value followed by the flow on every forward edge carrying flow.
+pub struct DimacsSolution<'a, G> {
+    pub value: f64,
+    pub graph: &'a G,
+}
+
+impl<'a, G> Display for DimacsSolution<'a, G>
+where
+    G: Iter + Base<Weight = FlowWeight<f64>>,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(f, "s {}", self.value)?;
+
+        for edge in self.graph.iter_edges() {
+            if !is_reverse_arc(edge.edge_id) && edge.weight.flow != 0.0 {
+                let from = edge.from().as_usize() + 1;
+                let to = edge.to().as_usize() + 1;
+                writeln!(f, "f {from} {to} {}", edge.weight.flow)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Dimacs, DimacsSolution, Problem};
+    use crate::{
+        algorithms::{capacity_scaling, cycle_canceling},
+        error::{GraphError, ParseErrorKind},
+        graph::{BalancedNode, FlowWeight},
+        prelude::{AdjacencyList, EdgeList, MultiAdjacencyList},
+        test::id,
+    };
+    use std::{fs, str::FromStr};
+
+    const MAX: &str = "c Fluss.txt
+p max 8 9
+n 1 s
+n 8 t
+a 1 2 4
+a 2 3 2
+a 3 5 2
+a 1 4 2
+a 4 5 3
+a 5 8 3
+a 4 6 2
+a 6 7 4
+a 7 8 3
+";
+
+    #[test]
+    fn dimacs_min_round_trip() {
+        let content = fs::read_to_string("data/Kostenminimal1.txt").unwrap();
+        let edge_list: EdgeList<BalancedNode<usize, f64>, FlowWeight<f64>, true> =
+            EdgeList::from_str(&content).unwrap();
+
+        let dimacs = Dimacs::min(edge_list.clone());
+        let parsed = Dimacs::from_str(&dimacs.to_string()).unwrap();
+        assert_eq!(parsed, dimacs);
+
+        let graph: AdjacencyList<_, _, true> = AdjacencyList::try_from(parsed.edge_list).unwrap();
+        assert_eq!(cycle_canceling(&graph), 3.0);
+    }
+
+    #[test]
+    fn dimacs_max() {
+        let dimacs = Dimacs::from_str(MAX).unwrap();
+
+        assert_eq!(dimacs.problem, Problem::Max);
+        assert_eq!((dimacs.source, dimacs.sink), (Some(0), Some(7)));
+        assert_eq!(Dimacs::from_str(&dimacs.to_string()).unwrap(), dimacs);
+
+        let graph: AdjacencyList<_, _, true> = AdjacencyList::try_from(dimacs.edge_list).unwrap();
+        let (residual_graph, total) = capacity_scaling(&graph, id(0), id(7)).unwrap();
+        assert_eq!(total, 4.0);
+
+        let solution = DimacsSolution {
+            value: total,
+            graph: &residual_graph,
+        }
+        .to_string();
+        let mut lines = solution.lines();
+
+        assert_eq!(lines.next(), Some("s 4"));
+        let outflow = lines
+            .filter(|line| line.starts_with("f 1 "))
+            .map(|line| line.split_whitespace().last().unwrap())
+            .map(|flow| flow.parse::<f64>().unwrap())
+            .sum::<f64>();
+        assert_eq!(outflow, 4.0);
+    }
+
+    #[test]
+    fn dimacs_errors() {
+        let result = Dimacs::from_str("p min 2 1\na 1 2 5 4 1\n");
+        assert!(matches!(
+            result,
+            Err(GraphError::Parse {
+                line: 2,
+                column: 9,
+                kind: ParseErrorKind::Expected(_),
+                ..
+            })
+        ));
+
+        let result = Dimacs::from_str("c zero is no node\np min 2 1\na 0 2 0 4 1\n");
+        assert!(matches!(
+            result,
+            Err(GraphError::Parse {
+                line: 3,
+                column: 3,
+                kind: ParseErrorKind::NodeOutOfRange { node_count: 2 },
+                ..
+            })
+        ));
+
+        let result = Dimacs::from_str("p max 2 2\nn 1 s\nn 2 t\na 1 2 4\n");
+        assert!(matches!(
+            result,
+            Err(GraphError::Parse {
+                line: 1,
+                kind: ParseErrorKind::ArcCount {
+                    expected: 2,
+                    found: 1
+                },
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn dimacs_parallel_arcs() {
+        let dimacs = Dimacs::from_str("p max 2 2\nn 1 s\nn 2 t\na 1 2 4\na 1 2 3\n").unwrap();
+        assert_eq!(dimacs.edge_list.edges.nnz(), 2);
+
+        let graph: MultiAdjacencyList<_, _, true> =
+            MultiAdjacencyList::try_from(dimacs.edge_list).unwrap();
+        let (_, total) = capacity_scaling(&graph, id(0), id(1)).unwrap();
+        assert_eq!(total, 7.0);
+    }
+
+    #[test]
+    fn dimacs_lower_bounds() {
+        // two units have to pass the expensive arc 1 -> 2
+        let content = "p min 3 3\nn 1 3\nn 3 -3\na 1 2 2 3 5\na 2 3 0 3 1\na 1 3 0 1 1\n";
+        let dimacs = Dimacs::from_str(content).unwrap();
+
+        let balances = dimacs.edge_list.nodes.iter().map(|node| node.balance);
+        assert_eq!(balances.collect::<Vec<_>>(), vec![1.0, 2.0, -3.0]);
+        assert_eq!(dimacs.edge_list.edges.get(0, 1).unwrap().capacity, 1.0);
+        assert_eq!(dimacs.cost_offset, 10.0);
+
+        let graph: AdjacencyList<_, _, true> = AdjacencyList::try_from(dimacs.edge_list).unwrap();
+        assert_eq!(cycle_canceling(&graph) + dimacs.cost_offset, 13.0);
+    }
+}
@@ -0,0 +1,258 @@
+use crate::{
+    algorithms::is_reverse_arc,
+    edge_list::EdgeDirections,
+    graph::{Base, Directed, EdgeCapacity, EdgeCost, EdgeFlow, Index, Iter},
+    prelude::{EdgeId, EdgeRef, NodeId, Tree},
+    structures::Route,
+};
+use std::{
+    collections::HashSet,
+    fmt::{self, Display, Formatter},
+};
+
+type NodeLabel<'a, G> = Box<dyn Fn(NodeId<<G as Base>::Id>, &<G as Base>::Node) -> String + 'a>;
+type EdgeLabel<'a, G> =
+    Box<dyn Fn(&EdgeRef<'_, <G as Base>::Id, <G as Base>::Weight>) -> String + 'a>;
+
+/// Writes a graph in the Graphviz DOT format.
+/// Overlays highlight the nodes and edges of algorithm results,
+/// undirected edges are written once.
+pub struct Dot<'a, G: Base> {
+    graph: &'a G,
+    node_label: NodeLabel<'a, G>,
+    edge_label: EdgeLabel<'a, G>,
+    nodes: HashSet<NodeId<G::Id>>,
+    edges: HashSet<EdgeId<G::Id>>,
+    hidden: HashSet<EdgeId<G::Id>>,
+}
+
+impl<'a, G> Dot<'a, G>
+where
+    G: Iter + Index + Directed,
+{
+    /// Labels the nodes with their ids and leaves the edges unlabeled
+    pub fn new(graph: &'a G) -> Self {
+        Self {
+            graph,
+            node_label: Box::new(|node_id, _| node_id.as_usize().to_string()),
+            edge_label: Box::new(|_| String::new()),
+            nodes: HashSet::new(),
+            edges: HashSet::new(),
+            hidden: HashSet::new(),
+        }
+    }
+
+    pub fn node_label(
+        &mut self,
+        label: impl Fn(NodeId<G::Id>, &G::Node) -> String + 'a,
+    ) -> &mut Self {
+        self.node_label = Box::new(label);
+        self
+    }
+
+    pub fn edge_label(
+        &mut self,
+        label: impl Fn(&EdgeRef<'_, G::Id, G::Weight>) -> String + 'a,
+    ) -> &mut Self {
+        self.edge_label = Box::new(label);
+        self
+    }
+
+    /// Highlights the nodes and edges of a spanning or search tree
+    pub fn tree(&mut self, tree: &Tree<G>) -> &mut Self {
+        self.nodes.extend(tree.node_ids());
+        self.edges
+            .extend(tree.edge_ids().map(|edge_id| edge_id.any()));
+        self
+    }
+
+    /// Highlights a tour or cycle including the edge back to its start
+    pub fn route(&mut self, route: &Route<G>) -> &mut Self {
+        self.nodes.extend(route.node_ids().iter().copied());
+        self.edges
+            .extend(route.edge_id_cycle().map(|edge_id| edge_id.any()));
+        self
+    }
+
+    /// Labels the edges of a residual graph with `flow/capacity @ cost`,
+    /// highlights the edges carrying flow and hides the reverse edges.
+    pub fn flow<C>(&mut self) -> &mut Self
+    where
+        C: Display + Default + PartialOrd,
+        G::Weight: EdgeFlow<Flow = C> + EdgeCapacity<Capacity = C> + EdgeCost<Cost = C>,
+    {
+        for edge in self.graph.iter_edges() {
+            if is_reverse_arc(edge.edge_id) {
+                self.hidden.insert(edge.edge_id);
+            } else if *edge.weight.flow() > C::default() {
+                self.edges.insert(edge.edge_id.any());
+            }
+        }
+
+        self.edge_label(|edge| {
+            let weight = edge.weight;
+            format!(
+                "{}/{} @ {}",
+                weight.flow(),
+                weight.capacity(),
+                weight.cost()
+            )
+        })
+    }
+
+    fn highlighted(&self, edge_id: EdgeId<G::Id>) -> bool {
+        self.edges.contains(&edge_id.any())
+            || (!G::directed() && self.edges.contains(&edge_id.any().rev()))
+    }
+}
+
+/// Quotes a label so that it can be used as an attribute value
+fn quote(label: &str) -> String {
+    format!("\"{}\"", label.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+impl<'a, G> Display for Dot<'a, G>
+where
+    G: Iter + Index + Directed,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let (kind, arrow) = if G::directed() {
+            ("digraph", "->")
+        } else {
+            ("graph", "--")
+        };
+
+        writeln!(f, "{kind} {{")?;
+
+        for (node_id, node) in self.graph.node_ids().zip(self.graph.iter_nodes()) {
+            let label = quote(&(self.node_label)(node_id, node));
+            write!(f, "    {} [label={label}", node_id.as_usize())?;
+
+            if self.nodes.contains(&node_id) {
+                write!(f, ", color=red")?;
+            }
+
+            writeln!(f, "];")?;
+        }
+
+        let directions = EdgeDirections::new(self.graph, G::directed());
+
+        for edge in self.graph.iter_edges() {
+            let (from, to) = (edge.from().as_usize(), edge.to().as_usize());
+
+            // the reverse half of an undirected edge is skipped
+            if self.hidden.contains(&edge.edge_id) || !directions.keep(from, to) {
+                continue;
+            }
+
+            let mut attributes = Vec::new();
+
+            let label = (self.edge_label)(&edge);
+            if !label.is_empty() {
+                attributes.push(format!("label={}", quote(&label)));
+            }
+
+            if self.highlighted(edge.edge_id) {
+                attributes.push("color=red, penwidth=2".to_string());
+            }
+
+            write!(f, "    {from} {arrow} {to}")?;
+            if !attributes.is_empty() {
+                write!(f, " [{}]", attributes.join(", "))?;
+            }
+            writeln!(f, ";")?;
+        }
+
+        writeln!(f, "}}")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Dot;
+    use crate::{
+        algorithms::{bellman_ford_cycle, kruskal, Mcf},
+        graph::{Count, Create, Index, Insert},
+        prelude::*,
+        test::{bgraph, digraph, id, undigraph},
+    };
+    use either::Either;
+
+    fn highlighted_edges(dot: &str) -> usize {
+        dot.lines()
+            .filter(|line| line.contains("--") || line.contains("->"))
+            .filter(|line| line.contains("color=red"))
+            .count()
+    }
+
+    #[test]
+    fn dot_undirected_tree() {
+        let graph: AdjacencyList<_, _> = undigraph("data/G_1_2.txt").unwrap();
+        let (tree, _) = kruskal(&graph);
+
+        let dot = Dot::new(&graph).tree(&tree).to_string();
+
+        assert!(dot.starts_with("graph {"));
+        assert_eq!(dot.matches(" -- ").count(), graph.edge_count());
+        assert_eq!(highlighted_edges(&dot), graph.node_count() - 1);
+    }
+
+    #[test]
+    fn dot_undirected_once() {
+        let mut graph = AdjacencyList::<usize, (), false, true>::with_nodes(0..3);
+        graph.insert_edge(id(0), id(0), ());
+        graph.insert_edge(id(2), id(1), ());
+        graph.insert_edge(id(1), id(2), ());
+
+        let dot = Dot::new(&graph).to_string();
+
+        assert_eq!(dot.matches(" -- ").count(), graph.edge_count());
+        assert!(dot.contains("    0 -- 0;"));
+        assert_eq!(dot.matches("    1 -- 2;").count(), 2);
+    }
+
+    #[test]
+    fn dot_negative_cycle() {
+        let graph: AdjacencyList<_, _, true> = digraph("data/Wege3.txt").unwrap();
+        let route = match bellman_ford_cycle(&graph, graph.node_ids().next().unwrap()) {
+            Either::Right(route) => route,
+            Either::Left(_) => panic!("Wege3 contains a negative cycle"),
+        };
+
+        let dot = Dot::new(&graph)
+            .route(&route)
+            .edge_label(|edge| edge.weight.to_string())
+            .to_string();
+
+        assert!(dot.starts_with("digraph {"));
+        assert_eq!(dot.matches(" -> ").count(), graph.edge_count());
+        assert_eq!(highlighted_edges(&dot), route.edge_id_cycle().count());
+    }
+
+    #[test]
+    fn dot_flow() {
+        let graph: AdjacencyList<_, _, true> = bgraph("data/Kostenminimal1.txt").unwrap();
+        let mut mcf = Mcf::init(&graph);
+        assert!(mcf.solvable());
+
+        let dot = Dot::new(&mcf.residual_graph).flow().to_string();
+
+        // the reverse edges of the residual graph are hidden
+        assert_eq!(dot.matches(" -> ").count(), graph.edge_count());
+        assert!(dot.contains("0 -> 2 [label=\"0/2 @ 2\"];"));
+        assert!(dot.contains("0 -> 4 [label=\"4/5 @ 1\", color=red, penwidth=2];"));
+        assert!(highlighted_edges(&dot) > 0);
+    }
+
+    #[test]
+    fn dot_labels() {
+        let graph: AdjacencyList<usize, f64, true> = digraph("data/Wege1.txt").unwrap();
+
+        let dot = Dot::new(&graph)
+            .node_label(|node_id, _| format!("\"{}\"", node_id.as_usize()))
+            .to_string();
+
+        assert!(dot.contains("    3 [label=\"\\\"3\\\"\"];"));
+        assert!(dot.contains("    3 -> 5;"));
+    }
+}
@@ -1,11 +1,16 @@
 use crate::{
-    error::GraphError,
-    graph::{BalancedNode, FlowWeight},
-    structures::SparseMatrix,
+    error::{GraphError, GraphResult, ParseErrorKind},
+    graph::{BalancedNode, Base, Count, FlowWeight, Index, Iter},
+    structures::{SparseMatrix, SparseMatrixBuilder},
+};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::{self, Display, Formatter},
+    str::{FromStr, SplitWhitespace},
 };
-use std::str::FromStr;
 
 #[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct EdgeList<N, W, const DI: bool = false> {
     pub(crate) nodes: Vec<N>,
     pub(crate) edges: SparseMatrix<W>,
@@ -13,60 +18,285 @@ pub struct EdgeList<N, W, const DI: bool = false> {
 }
 
 impl<W, const DI: bool> EdgeList<usize, W, DI> {
+    /// Parallel edges are kept, see [EdgeList::with_edges] to merge or reject them.
     pub fn with(list: impl Iterator<Item = (usize, usize, W)>, node_count: usize) -> Self {
-        let nodes = (0..node_count).collect();
-        let mut edges = SparseMatrix::with_capacity(node_count, node_count);
+        let mut edges = SparseMatrixBuilder::with_capacity(node_count, node_count);
 
         for (parent, child, weight) in list {
             edges.insert(parent, child, weight);
         }
 
+        Self::with_edges(edges.build_all())
+    }
+
+    /// Uses edges which the caller has built, with [SparseMatrixBuilder::build]
+    /// or [SparseMatrixBuilder::build_unique] to merge or reject parallel edges.
+    pub fn with_edges(edges: SparseMatrix<W>) -> Self {
+        let node_count = edges.row_count();
+        assert_eq!(
+            node_count,
+            edges.col_count(),
+            "the edge matrix must be square"
+        );
+
         Self {
-            nodes,
+            nodes: (0..node_count).collect(),
             edges,
             node_count,
         }
     }
 }
 
-impl<const DI: bool> FromStr for EdgeList<BalancedNode<usize, f64>, FlowWeight<f64>, DI> {
-    type Err = GraphError;
+impl<N, W, const DI: bool> EdgeList<N, W, DI> {
+    /// Fails with [GraphError::EdgeAlreadyExists] on the first parallel edge,
+    /// for the graphs which can not tell parallel edges apart.
+    /// Undirected edges are parallel in either direction.
+    pub(crate) fn ensure_simple(&self) -> GraphResult<()> {
+        let mut seen = HashSet::new();
+
+        for (from, to, _) in self.edges.iter() {
+            let edge = if DI {
+                (from, to)
+            } else {
+                (from.min(to), from.max(to))
+            };
+
+            if !seen.insert(edge) {
+                let (from, to) = edge;
+                return Err(GraphError::EdgeAlreadyExists {
+                    from,
+                    to,
+                    line: None,
+                });
+            }
+        }
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut lines = s.lines();
+        Ok(())
+    }
 
-        let node_count = lines.next().ok_or(GraphError::BadEdgeListFormat)?;
-        let node_count = usize::from_str_radix(node_count, 10)?;
+    /// Copies the nodes and edges of any graph, gaps in its node ids are closed.
+    /// Undirected edges are kept once, parallel edges are kept.
+    pub fn from_graph<G>(graph: &G) -> Self
+    where
+        N: Clone,
+        W: Clone,
+        G: Index + Iter + Count + Base<Node = N, Weight = W>,
+    {
+        let mut index = vec![None; graph.node_bound()];
+        for (i, node_id) in graph.node_ids().enumerate() {
+            index[node_id.as_usize()] = Some(i);
+        }
 
-        let nodes = (0..node_count)
-            .map(|node_id| -> Result<BalancedNode<usize, f64>, GraphError> {
-                let balance_str = lines.next().ok_or(GraphError::BadEdgeListFormat)?;
-                let balance = balance_str.parse::<f64>()?;
-                let node = BalancedNode::new(node_id, balance);
-                Ok(node)
-            })
-            .collect::<Result<_, _>>()?;
+        let nodes: Vec<N> = graph.iter_nodes().cloned().collect();
+        let directions = EdgeDirections::new(graph, DI);
+
+        let mut edges = SparseMatrixBuilder::with_capacity(nodes.len(), nodes.len());
+        for edge in graph.iter_edges() {
+            let (from, to) = (edge.from().as_usize(), edge.to().as_usize());
+
+            if directions.keep(from, to) {
+                edges.insert(
+                    index[from].unwrap(),
+                    index[to].unwrap(),
+                    edge.weight.clone(),
+                );
+            }
+        }
+
+        Self {
+            node_count: nodes.len(),
+            edges: edges.build_all(),
+            nodes,
+        }
+    }
+}
+
+/// Picks the edges of a graph to write once.
+/// Undirected graphs store an edge in both directions and a self loop once.
+pub(crate) struct EdgeDirections {
+    /// How often every direction is stored, `None` for directed graphs
+    counts: Option<HashMap<(usize, usize), usize>>,
+}
+
+impl EdgeDirections {
+    pub(crate) fn new<G: Iter>(graph: &G, directed: bool) -> Self {
+        let counts = (!directed).then(|| {
+            let mut counts = HashMap::new();
+            for edge in graph.iter_edges() {
+                let direction = (edge.from().as_usize(), edge.to().as_usize());
+                *counts.entry(direction).or_insert(0) += 1;
+            }
+            counts
+        });
+
+        Self { counts }
+    }
+
+    /// Keeps the direction which is stored more often, or the one leaving the smaller node,
+    /// so parallel undirected edges are kept as often as they are stored.
+    pub(crate) fn keep(&self, from: usize, to: usize) -> bool {
+        let Some(counts) = &self.counts else {
+            return true;
+        };
+
+        let count = |direction| counts.get(&direction).copied().unwrap_or(0);
+        let (forward, backward) = (count((from, to)), count((to, from)));
+
+        from == to || forward > backward || (forward == backward && from < to)
+    }
+}
+
+/// The whitespace separated tokens of a single line, used to locate parse errors.
+pub(crate) struct Tokens<'a> {
+    pub(crate) line: usize,
+    pub(crate) text: &'a str,
+    split: SplitWhitespace<'a>,
+}
+
+impl<'a> Tokens<'a> {
+    pub(crate) fn new(line: usize, text: &'a str) -> Self {
+        Self {
+            line,
+            text,
+            split: text.split_whitespace(),
+        }
+    }
+
+    /// Numbers the lines starting with one
+    pub(crate) fn lines(s: &'a str) -> impl Iterator<Item = Self> {
+        s.lines()
+            .enumerate()
+            .map(|(index, text)| Self::new(index + 1, text))
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.text.split_whitespace().count()
+    }
+
+    /// Points at the token or behind the end of the line if the token is missing
+    pub(crate) fn error(&self, token: Option<&str>, kind: ParseErrorKind) -> GraphError {
+        let offset = match token {
+            // tokens are slices of the line, so the pointers give their position
+            Some(token) => token.as_ptr() as usize - self.text.as_ptr() as usize,
+            None => self.text.len(),
+        };
+
+        GraphError::Parse {
+            line: self.line,
+            column: self.text[..offset].chars().count() + 1,
+            token: token.unwrap_or_default().to_string(),
+            kind,
+        }
+    }
+
+    pub(crate) fn token(&mut self) -> GraphResult<&'a str> {
+        match self.split.next() {
+            Some(token) => Ok(token),
+            None => Err(self.error(None, ParseErrorKind::MissingToken)),
+        }
+    }
+
+    pub(crate) fn parse<T>(&mut self) -> GraphResult<T>
+    where
+        T: FromStr,
+        T::Err: Into<ParseErrorKind>,
+    {
+        let token = self.token()?;
+        self.value(token)
+    }
+
+    /// Parses a token taken from this line
+    pub(crate) fn value<T>(&self, token: &str) -> GraphResult<T>
+    where
+        T: FromStr,
+        T::Err: Into<ParseErrorKind>,
+    {
+        token
+            .parse()
+            .map_err(|err: T::Err| self.error(Some(token), err.into()))
+    }
 
-        let mut edges = SparseMatrix::with_capacity(node_count, node_count);
+    pub(crate) fn node(&mut self, node_count: usize) -> GraphResult<usize> {
+        let token = self.token()?;
+        let node = self.value::<usize>(token)?;
 
-        for line in lines {
-            let mut split = line.split_whitespace();
-            let from = split.next().ok_or(GraphError::BadEdgeListFormat)?;
-            let to = split.next().ok_or(GraphError::BadEdgeListFormat)?;
-            let cost = split.next().ok_or(GraphError::BadEdgeListFormat)?;
-            let capacity = split.next().ok_or(GraphError::BadEdgeListFormat)?;
+        if node >= node_count {
+            return Err(self.error(Some(token), ParseErrorKind::NodeOutOfRange { node_count }));
+        }
+
+        Ok(node)
+    }
+}
+
+/// Parses the node count in the first line
+fn parse_node_count<'a>(lines: &mut impl Iterator<Item = Tokens<'a>>) -> GraphResult<usize> {
+    lines.next().unwrap_or_else(|| Tokens::new(1, "")).parse()
+}
+
+/// Parses the remaining lines as `from to` followed by the tokens read by `weight`
+fn parse_edges<'a, W>(
+    lines: impl Iterator<Item = Tokens<'a>>,
+    node_count: usize,
+    weight: impl Fn(&mut Tokens<'a>) -> GraphResult<W>,
+) -> GraphResult<Vec<(usize, usize, W)>> {
+    lines
+        .map(|mut tokens| {
+            let from = tokens.node(node_count)?;
+            let to = tokens.node(node_count)?;
+            let weight = weight(&mut tokens)?;
+            Ok((from, to, weight))
+        })
+        .collect()
+}
+
+impl<const DI: bool> FromStr for EdgeList<BalancedNode<usize, f64>, FlowWeight<f64>, DI> {
+    type Err = GraphError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = Tokens::lines(s).peekable();
+        let node_count = parse_node_count(&mut lines)?;
+
+        let balance_count = |tokens: &Tokens, found| {
+            let first = tokens.text.split_whitespace().next();
+            let kind = ParseErrorKind::BalanceCount {
+                expected: node_count,
+                found,
+            };
+            tokens.error(first, kind)
+        };
+
+        let mut nodes = Vec::with_capacity(node_count);
+        for node_id in 0..node_count {
+            // the balances follow the node count, one per line
+            let mut tokens = match lines.next() {
+                Some(tokens) if tokens.len() == 1 => tokens,
+                Some(tokens) => return Err(balance_count(&tokens, node_id)),
+                None => return Err(balance_count(&Tokens::new(node_id + 2, ""), node_id)),
+            };
+
+            let balance = tokens.parse::<f64>()?;
+            nodes.push(BalancedNode::new(node_id, balance));
+        }
+
+        if let Some(tokens) = lines.next_if(|tokens| tokens.len() == 1) {
+            let extra = 1 + lines.take_while(|tokens| tokens.len() == 1).count();
+            return Err(balance_count(&tokens, node_count + extra));
+        }
 
-            let from = from.parse::<usize>()?;
-            let to = to.parse::<usize>()?;
-            let cost = cost.parse::<f64>()?;
-            let capacity = capacity.parse::<f64>()?;
+        let edge_list = parse_edges(lines, node_count, |tokens| {
+            let cost = tokens.parse::<f64>()?;
+            let capacity = tokens.parse::<f64>()?;
+            Ok(FlowWeight::new(capacity, cost, 0.0))
+        })?;
 
-            edges.insert(from, to, FlowWeight::new(capacity, cost, 0.0));
+        let mut edges = SparseMatrixBuilder::with_capacity(node_count, node_count);
+        for (from, to, weight) in edge_list {
+            edges.insert(from, to, weight);
         }
 
         Ok(Self {
             nodes,
-            edges,
+            edges: edges.build_all(),
             node_count,
         })
     }
@@ -76,22 +306,9 @@ impl FromStr for EdgeList<usize, ()> {
     type Err = GraphError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut lines = s.lines();
-
-        let node_count = lines.next().ok_or(GraphError::BadEdgeListFormat)?;
-        let node_count = usize::from_str_radix(node_count, 10)?;
-
-        let edge_list = lines
-            .map(|line| -> Result<(usize, usize, ()), Self::Err> {
-                let mut split = line.split_whitespace();
-                let from = split.next().ok_or(GraphError::BadEdgeListFormat)?;
-                let to = split.next().ok_or(GraphError::BadEdgeListFormat)?;
-
-                let from = from.parse::<usize>()?;
-                let to = to.parse::<usize>()?;
-                Ok((from, to, ()))
-            })
-            .collect::<Result<Vec<_>, _>>()?;
+        let mut lines = Tokens::lines(s);
+        let node_count = parse_node_count(&mut lines)?;
+        let edge_list = parse_edges(lines, node_count, |_| Ok(()))?;
 
         Ok(Self::with(edge_list.into_iter(), node_count))
     }
@@ -101,24 +318,9 @@ impl<const DI: bool> FromStr for EdgeList<usize, f64, DI> {
     type Err = GraphError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut lines = s.lines();
-
-        let node_count = lines.next().ok_or(GraphError::BadEdgeListFormat)?;
-        let node_count = usize::from_str_radix(node_count, 10)?;
-
-        let edge_list = lines
-            .map(|line| -> Result<(usize, usize, f64), Self::Err> {
-                let mut split = line.split_whitespace();
-                let from = split.next().ok_or(GraphError::BadEdgeListFormat)?;
-                let to = split.next().ok_or(GraphError::BadEdgeListFormat)?;
-                let weight = split.next().ok_or(GraphError::BadEdgeListFormat)?;
-
-                let from = from.parse::<usize>()?;
-                let to = to.parse::<usize>()?;
-                let weight: f64 = weight.parse::<f64>()?;
-                Ok((from, to, weight))
-            })
-            .collect::<Result<Vec<_>, _>>()?;
+        let mut lines = Tokens::lines(s);
+        let node_count = parse_node_count(&mut lines)?;
+        let edge_list = parse_edges(lines, node_count, |tokens| tokens.parse::<f64>())?;
 
         Ok(Self::with(edge_list.into_iter(), node_count))
     }
@@ -128,34 +330,70 @@ impl<const DI: bool> FromStr for EdgeList<usize, f32, DI> {
     type Err = GraphError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut lines = s.lines();
-
-        let node_count = lines.next().ok_or(GraphError::BadEdgeListFormat)?;
-        let node_count = usize::from_str_radix(node_count, 10)?;
-
-        let edge_list = lines
-            .map(|line| -> Result<(usize, usize, f32), Self::Err> {
-                let mut split = line.split_whitespace();
-                let from = split.next().ok_or(GraphError::BadEdgeListFormat)?;
-                let to = split.next().ok_or(GraphError::BadEdgeListFormat)?;
-                let weight = split.next().ok_or(GraphError::BadEdgeListFormat)?;
-
-                let from = from.parse::<usize>()?;
-                let to = to.parse::<usize>()?;
-                let weight = weight.parse::<f32>()?;
-                Ok((from, to, weight))
-            })
-            .collect::<Result<Vec<_>, _>>()?;
+        let mut lines = Tokens::lines(s);
+        let node_count = parse_node_count(&mut lines)?;
+        let edge_list = parse_edges(lines, node_count, |tokens| tokens.parse::<f32>())?;
 
         Ok(Self::with(edge_list.into_iter(), node_count))
     }
 }
 
+/// Writes the edges as `from to` followed by the tokens written by `weight`
+fn write_edges<W>(
+    f: &mut Formatter<'_>,
+    edges: &SparseMatrix<W>,
+    weight: impl Fn(&mut Formatter<'_>, &W) -> fmt::Result,
+) -> fmt::Result {
+    for (from, to, w) in edges.iter() {
+        write!(f, "{from}\t{to}")?;
+        weight(f, w)?;
+        writeln!(f)?;
+    }
+
+    Ok(())
+}
+
+impl<const DI: bool> Display for EdgeList<BalancedNode<usize, f64>, FlowWeight<f64>, DI> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", self.node_count)?;
+
+        for node in &self.nodes {
+            writeln!(f, "{}", node.balance)?;
+        }
+
+        write_edges(f, &self.edges, |f, weight| {
+            write!(f, "\t{}\t{}", weight.cost, weight.capacity)
+        })
+    }
+}
+
+impl<const DI: bool> Display for EdgeList<usize, (), DI> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", self.node_count)?;
+        write_edges(f, &self.edges, |_, _| Ok(()))
+    }
+}
+
+impl<const DI: bool> Display for EdgeList<usize, f64, DI> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", self.node_count)?;
+        write_edges(f, &self.edges, |f, weight| write!(f, "\t{weight}"))
+    }
+}
+
+impl<const DI: bool> Display for EdgeList<usize, f32, DI> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", self.node_count)?;
+        write_edges(f, &self.edges, |f, weight| write!(f, "\t{weight}"))
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::{
-        graph::{BalancedNode, FlowWeight},
-        prelude::AdjacencyList,
+        error::{GraphError, ParseErrorKind},
+        graph::{BalancedNode, Count, Create, FlowWeight, Get, Index, Insert},
+        prelude::{AdjacencyList, AdjacencyMatrix, NodeId},
     };
 
     use super::EdgeList;
@@ -189,4 +427,208 @@ mod test {
             AdjacencyList::<BalancedNode<usize, f64>, FlowWeight<f64>, true>::try_from(edge_list)
                 .unwrap();
     }
+
+    #[test]
+    fn parse_error_position() {
+        let result = EdgeList::<usize, f64>::from_str("3\n0 1 1.5\n1\t2  x.5\n");
+
+        match result {
+            Err(GraphError::Parse {
+                line,
+                column,
+                token,
+                kind: ParseErrorKind::Float(_),
+            }) => {
+                assert_eq!(line, 3);
+                assert_eq!(column, 6);
+                assert_eq!(token, "x.5");
+            }
+            other => panic!("unexpected result {other:?}"),
+        }
+
+        let result = EdgeList::<usize, ()>::from_str("3\n0 1\n2\n");
+        assert!(matches!(
+            result,
+            Err(GraphError::Parse {
+                line: 3,
+                column: 2,
+                kind: ParseErrorKind::MissingToken,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn parse_error_node_out_of_range() {
+        let result = EdgeList::<usize, f64, true>::from_str("3\n0 1 1.0\n1 3 1.0\n");
+
+        assert!(matches!(
+            result,
+            Err(GraphError::Parse {
+                line: 3,
+                column: 3,
+                kind: ParseErrorKind::NodeOutOfRange { node_count: 3 },
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn parse_error_balance_count() {
+        type Balanced = EdgeList<BalancedNode<usize, f64>, FlowWeight<f64>, true>;
+
+        let result = Balanced::from_str("3\n1.0\n-1.0\n0 1 1.0 2.0\n");
+        assert!(matches!(
+            result,
+            Err(GraphError::Parse {
+                line: 4,
+                kind: ParseErrorKind::BalanceCount {
+                    expected: 3,
+                    found: 2
+                },
+                ..
+            })
+        ));
+
+        let result = Balanced::from_str("2\n1.0\n-1.0\n0.0\n0 1 1.0 2.0\n");
+        assert!(matches!(
+            result,
+            Err(GraphError::Parse {
+                line: 4,
+                kind: ParseErrorKind::BalanceCount {
+                    expected: 2,
+                    found: 3
+                },
+                ..
+            })
+        ));
+
+        let result = Balanced::from_str("2\n1.0\n");
+        assert!(matches!(
+            result,
+            Err(GraphError::Parse {
+                line: 3,
+                kind: ParseErrorKind::BalanceCount {
+                    expected: 2,
+                    found: 1
+                },
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn parallel_edges() {
+        let content = "2\n0 1 1\n1 0 1\n0 1 2\n";
+        let edge_list = EdgeList::<usize, f64, true>::from_str(content).unwrap();
+        assert_eq!(edge_list.edges.nnz(), 3);
+
+        let multi = AdjacencyList::<usize, f64, true, true>::try_from(edge_list.clone()).unwrap();
+        assert_eq!(multi.edge_count(), 3);
+
+        let result = AdjacencyList::<usize, f64, true>::try_from(edge_list.clone());
+        assert!(matches!(
+            result,
+            Err(GraphError::EdgeAlreadyExists {
+                from: 0,
+                to: 1,
+                line: None
+            })
+        ));
+
+        let result = AdjacencyMatrix::<usize, f64, true>::try_from(edge_list);
+        assert!(matches!(result, Err(GraphError::EdgeAlreadyExists { .. })));
+
+        // undirected edges are parallel in either direction
+        let edge_list = EdgeList::<usize, f64>::with([(0, 1, 1.0), (1, 0, 2.0)].into_iter(), 2);
+        let result = AdjacencyList::<usize, f64>::try_from(edge_list);
+        assert!(matches!(
+            result,
+            Err(GraphError::EdgeAlreadyExists {
+                from: 0,
+                to: 1,
+                line: None
+            })
+        ));
+    }
+
+    #[test]
+    fn write_directed_round_trip() {
+        let content = fs::read_to_string("data/G_1_2.txt").unwrap();
+        let edge_list = EdgeList::<usize, f64, true>::from_str(&content).unwrap();
+        let adj_list = AdjacencyList::<usize, f64, true>::try_from(edge_list.clone()).unwrap();
+
+        let written = EdgeList::from(&adj_list).to_string();
+        let parsed = EdgeList::<usize, f64, true>::from_str(&written).unwrap();
+
+        assert_eq!(parsed, edge_list);
+    }
+
+    #[test]
+    fn write_undirected_once() {
+        let content = fs::read_to_string("data/Graph2.txt").unwrap();
+        let edge_list = EdgeList::<usize, ()>::from_str(&content).unwrap();
+        let adj_mat = AdjacencyMatrix::<usize, ()>::try_from(edge_list).unwrap();
+
+        let written = EdgeList::from(&adj_mat).to_string();
+        assert_eq!(written.lines().count(), adj_mat.edge_count() + 1);
+
+        let parsed = EdgeList::<usize, ()>::from_str(&written).unwrap();
+        let rebuilt = AdjacencyMatrix::<usize, ()>::try_from(parsed).unwrap();
+
+        assert_eq!(rebuilt.node_count(), adj_mat.node_count());
+        assert_eq!(rebuilt.edge_count(), adj_mat.edge_count());
+        assert!(adj_mat
+            .edge_ids()
+            .all(|edge_id| rebuilt.contains_edge_id(edge_id)));
+    }
+
+    #[test]
+    fn write_balanced_round_trip() {
+        let content = fs::read_to_string("data/Kostenminimal_gross1.txt").unwrap();
+        let edge_list: EdgeList<BalancedNode<usize, f64>, FlowWeight<f64>, true> =
+            EdgeList::from_str(&content).unwrap();
+        let adj_list: AdjacencyList<_, _, true> =
+            AdjacencyList::try_from(edge_list.clone()).unwrap();
+
+        let written = EdgeList::from(&adj_list).to_string();
+        let parsed = EdgeList::from_str(&written).unwrap();
+
+        assert_eq!(parsed, edge_list);
+    }
+
+    #[test]
+    fn from_graph_parallel_edges() {
+        let mut multi = AdjacencyList::<usize, f64, true, true>::with_nodes(0..2);
+        let (zero, one) = (NodeId::new_unchecked(0), NodeId::new_unchecked(1));
+        multi.insert_edge(zero, one, 1.0);
+        multi.insert_edge(zero, one, 2.0);
+
+        let edge_list = EdgeList::from(&multi);
+        assert_eq!(edge_list.edges.nnz(), 2);
+
+        // undirected edges are kept once in either direction, the self loop is stored once
+        let mut multi = AdjacencyList::<usize, f64, false, true>::with_nodes(0..2);
+        multi.insert_edge(zero, zero, 1.0);
+        multi.insert_edge(zero, one, 2.0);
+        multi.insert_edge(one, zero, 3.0);
+
+        let edge_list = EdgeList::from(&multi);
+        assert_eq!(
+            edge_list.edges.iter().collect::<Vec<_>>(),
+            vec![(0, 0, &1.0), (0, 1, &2.0), (0, 1, &3.0)]
+        );
+    }
+
+    #[test]
+    fn from_graph_undirected_once() {
+        let mut adj_mat = AdjacencyMatrix::<usize, f64>::with_nodes(0..3);
+        adj_mat.insert_edge(NodeId::new_unchecked(2), NodeId::new_unchecked(1), 1.0);
+
+        let edge_list = EdgeList::from(&adj_mat);
+        assert_eq!(
+            edge_list.edges.iter().collect::<Vec<_>>(),
+            vec![(1, 2, &1.0)]
+        );
+    }
 }
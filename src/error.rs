@@ -5,12 +5,23 @@ pub type GraphResult<T> = Result<T, GraphError>;
 
 #[derive(Debug, Error)]
 pub enum GraphError {
-    #[error("The edge between {from:?} and {to:?} already exists.")]
-    EdgeAlreadyExists { from: usize, to: usize },
+    #[error("The edge between {from:?} and {to:?} already exists{}.", on_line(.line))]
+    EdgeAlreadyExists {
+        from: usize,
+        to: usize,
+        line: Option<usize>,
+    },
     #[error("Two sided edge forbidden between {from:?} and {to:?} in directed graph.")]
     TwoSidedEdgeForbidden { from: usize, to: usize },
     #[error("The given edge list has a bad format")]
     BadEdgeListFormat,
+    #[error("Line {line}, column {column} `{token}`: {kind}")]
+    Parse {
+        line: usize,
+        column: usize,
+        token: String,
+        kind: ParseErrorKind,
+    },
     #[error("ParseIntError: {0}")]
     ParseIntError(#[from] ParseIntError),
     #[error("ParseFloatError: {0}")]
@@ -21,6 +32,57 @@ pub enum GraphError {
     NoCycle,
     #[error("IoError: {0}")]
     Io(#[from] std::io::Error),
+    #[error("XmlError: {0}")]
+    Xml(#[from] roxmltree::Error),
+    #[error("The element {element} has no {name} attribute")]
+    MissingAttribute { element: String, name: String },
+    #[error("The attribute {name} has the invalid value {value:?}")]
+    InvalidAttribute { name: String, value: String },
+    #[error("The node {0} is not declared")]
+    UnknownNode(String),
+    #[error("The edgedefault of the graph does not match, directed: {directed}")]
+    EdgeDefault { directed: bool },
+    #[error("The snapshot is invalid: {0}")]
+    InvalidSnapshot(&'static str),
+    #[error("The snapshot has another {field}, expected tag {expected} but found {found}")]
+    SnapshotMismatch {
+        field: &'static str,
+        expected: u8,
+        found: u8,
+    },
     #[error("Minimal cost flow not solvable")]
     McfNotSolvable,
+    #[error("The graph contains a negative cycle")]
+    NegativeCycle,
+    #[error("The heuristic is inconsistent on the edge from {from} to {to}")]
+    InconsistentHeuristic { from: usize, to: usize },
+    #[error("The heuristic is not zero at the target {0}")]
+    HeuristicAtTarget(usize),
+}
+
+/// The reason a token of an input file was rejected.
+#[derive(Debug, Error)]
+pub enum ParseErrorKind {
+    #[error("expected another value")]
+    MissingToken,
+    #[error("{0}")]
+    Int(#[from] ParseIntError),
+    #[error("{0}")]
+    Float(#[from] ParseFloatError),
+    #[error("node id is out of range for {node_count} nodes")]
+    NodeOutOfRange { node_count: usize },
+    #[error("expected {expected} balances but found {found}")]
+    BalanceCount { expected: usize, found: usize },
+    #[error("expected {expected} arcs but found {found}")]
+    ArcCount { expected: usize, found: usize },
+    #[error("expected {expected} values in the row but found {found}")]
+    RowLength { expected: usize, found: usize },
+    #[error("expected {0}")]
+    Expected(&'static str),
+}
+
+/// The location of an error in an input file, if it has one
+fn on_line(line: &Option<usize>) -> String {
+    line.map(|line| format!(" on line {line}"))
+        .unwrap_or_default()
 }
@@ -2,6 +2,7 @@ use super::{EdgeId, Identifier, NodeId};
 use std::fmt::Debug;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Edge<Id: Identifier, Weight> {
     pub edge_id: EdgeId<Id>,
     pub weight: Weight,
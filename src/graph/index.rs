@@ -1,17 +1,17 @@
 use std::{fmt::Debug, hash::Hash};
 
 pub trait Identifier:
-    From<usize> + Into<usize> + Hash + PartialEq + Eq + PartialOrd + Ord + Copy + Clone + Debug
+    Into<usize> + Hash + PartialEq + Eq + PartialOrd + Ord + Copy + Clone + Debug
 {
 }
 
-impl<
-        T: From<usize> + Into<usize> + Hash + PartialEq + Eq + PartialOrd + Ord + Copy + Clone + Debug,
-    > Identifier for T
+impl<T: Into<usize> + Hash + PartialEq + Eq + PartialOrd + Ord + Copy + Clone + Debug> Identifier
+    for T
 {
 }
 
 #[derive(Hash, PartialEq, Eq, PartialOrd, Ord, Copy, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct NodeId<Id: Identifier>(Id);
 
 impl<Id: Identifier> NodeId<Id> {
@@ -22,17 +22,34 @@ impl<Id: Identifier> NodeId<Id> {
     pub fn as_usize(&self) -> usize {
         self.0.into()
     }
+
+    pub fn raw(&self) -> Id {
+        self.0
+    }
 }
 
+/// Identifies an edge by its endpoints.
+/// Multigraphs additionally assign every edge a unique key,
+/// an id without a key stands for any edge between the two nodes.
 #[derive(Hash, PartialEq, Eq, PartialOrd, Ord, Copy, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct EdgeId<Id: Identifier> {
     from: NodeId<Id>,
     to: NodeId<Id>,
+    key: Option<usize>,
 }
 
 impl<Id: Identifier> EdgeId<Id> {
     pub(crate) fn new_unchecked(from: NodeId<Id>, to: NodeId<Id>) -> Self {
-        Self { from, to }
+        Self {
+            from,
+            to,
+            key: None,
+        }
+    }
+
+    pub(crate) fn with_key(from: NodeId<Id>, to: NodeId<Id>, key: Option<usize>) -> Self {
+        Self { from, to, key }
     }
 
     pub fn contains(&self, node_id: NodeId<Id>) -> bool {
@@ -47,15 +64,34 @@ impl<Id: Identifier> EdgeId<Id> {
         self.to
     }
 
+    pub fn key(&self) -> Option<usize> {
+        self.key
+    }
+
     pub fn raw(&self) -> (NodeId<Id>, NodeId<Id>) {
         (self.from, self.to)
     }
 
+    /// Returns the id without its key, which matches any edge between the two nodes.
+    pub fn any(&self) -> EdgeId<Id> {
+        Self::new_unchecked(self.from, self.to)
+    }
+
+    /// Returns true if this id refers to `other`.
+    /// Ids without a key match every edge between the same nodes.
+    pub fn matches(&self, other: &EdgeId<Id>) -> bool {
+        self.from == other.from
+            && self.to == other.to
+            && (self.key.is_none() || self.key == other.key)
+    }
+
+    /// Reverses the direction, the key is kept.
     pub fn rev(&self) -> EdgeId<Id> {
-        let Self { from, to } = self;
+        let Self { from, to, key } = self;
         Self {
             from: *to,
             to: *from,
+            key: *key,
         }
     }
 }
@@ -1,3 +1,4 @@
+use num_traits::Float;
 use std::fmt::Debug;
 
 pub use edge::*;
@@ -8,12 +9,14 @@ pub use weight::*;
 
 use crate::{
     algorithms::{
-        bellman_ford, bellman_ford_between, bfs, bfs_scc, branch_bound, branch_bound_rec,
-        brute_force, dfs, dfs_scc, dijkstra, dijkstra_between, double_tree, edmonds_karp, kruskal,
-        nearest_neighbor, nearest_neighbor_from_first, prim,
+        astar, bellman_ford, bellman_ford_between, bfs, bfs_scc, branch_bound, branch_bound_rec,
+        brute_force, capacity_scaling, cycles, dfs, dfs_scc, dijkstra, dijkstra_between,
+        double_tree, edmonds_karp, find_cycle, floyd_warshall, has_cycle, johnson, kruskal,
+        nearest_neighbor, nearest_neighbor_from_first, prim, Cycles, ResidualGraph,
     },
+    error::GraphResult,
     prelude::{Tree},
-    structures::{Distances,  Route},
+    structures::{AllPairs, Distances,  Route},
 };
 
 mod edge;
@@ -66,6 +69,36 @@ pub trait Graph<N: Node, W: Weight>:
         dijkstra(self, from, to)
     }
 
+    fn astar(
+        &self,
+        from: NodeId<Self::Id>,
+        to: NodeId<Self::Id>,
+        heuristic: impl Fn(NodeId<Self::Id>) -> W::Cost,
+    ) -> GraphResult<Option<Distances<W::Cost, Self>>> {
+        astar(self, from, to, heuristic)
+    }
+
+    fn capacity_scaling(
+        &self,
+        source: NodeId<Self::Id>,
+        sink: NodeId<Self::Id>,
+    ) -> Option<(ResidualGraph<N, W::Cost>, W::Cost)>
+    where
+        Self: Base<Id = usize>,
+        W: EdgeCapacity<Capacity = W::Cost> + EdgeFlow<Flow = W::Cost>,
+        W::Cost: Float,
+    {
+        capacity_scaling(self, source, sink)
+    }
+
+    fn johnson(&self) -> GraphResult<Vec<Distances<W::Cost, Self>>> {
+        johnson(self)
+    }
+
+    fn floyd_warshall(&self) -> GraphResult<AllPairs<W::Cost, Self>> {
+        floyd_warshall(self)
+    }
+
     // fn edmonds_karp(&self, from: NodeId<Self::Id>, to: NodeId<Self::Id>) -> W::Cost 
     // where Self::Id = usize{
     //     edmonds_karp(self, from, to)
@@ -99,6 +132,18 @@ pub trait Graph<N: Node, W: Weight>:
         bfs(self, from)
     }
 
+    fn has_cycle(&self) -> bool {
+        has_cycle(self)
+    }
+
+    fn find_cycle(&self) -> Option<Route<Self>> {
+        find_cycle(self)
+    }
+
+    fn cycles(&self) -> Cycles<Self> {
+        cycles(self)
+    }
+
     fn nearest_neighbor(&self, start: NodeId<Self::Id>) -> Option<(Route<Self>, W::Cost)> {
         nearest_neighbor(self, start)
     }
@@ -163,4 +208,16 @@ pub trait WeightlessGraph<N>:
     fn bfs(&self, from: NodeId<Self::Id>) -> Tree<Self> {
         bfs(self, from)
     }
+
+    fn has_cycle(&self) -> bool {
+        has_cycle(self)
+    }
+
+    fn find_cycle(&self) -> Option<Route<Self>> {
+        find_cycle(self)
+    }
+
+    fn cycles(&self) -> Cycles<Self> {
+        cycles(self)
+    }
 }
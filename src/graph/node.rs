@@ -1,6 +1,7 @@
 use std::fmt::Debug;
 
 #[derive(Default, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BalancedNode<N, W> {
     pub node: N,
     pub balance: W,
@@ -117,3 +117,109 @@ pub fn graph_index_adjacent<G: Graph<usize, f32>>() {
     let one_adj_ids = graph.adjacent_node_ids(one).collect::<Vec<_>>();
     assert_eq!(one_adj_ids, vec![two, three]);
 }
+
+pub fn graph_remove_edge<G: Graph<usize, f32>>() {
+    let mut graph = G::with_capacity(3, 2);
+    let one = graph.insert_node(1);
+    let two = graph.insert_node(2);
+    let three = graph.insert_node(3);
+
+    let one_two = graph.insert_edge(one, two, 2.0);
+    graph.insert_edge(two, three, 3.0);
+
+    assert_eq!(graph.remove_edge(one_two), Some(2.0));
+    assert_eq!(graph.remove_edge(one_two), None);
+
+    assert!(graph.contains_edge(one, two).is_none());
+    assert!(graph.contains_edge(two, three).is_some());
+
+    if G::directed() {
+        let two_one = graph.insert_edge(two, one, 4.0);
+        assert_eq!(graph.weight(two_one), Some(&4.0));
+        assert!(graph.contains_edge(one, two).is_none());
+    } else {
+        assert!(graph.contains_edge(two, one).is_none());
+    }
+}
+
+pub fn graph_remove_node<G: Graph<usize, f32>>() {
+    let mut graph = G::with_capacity(4, 3);
+    let one = graph.insert_node(1);
+    let two = graph.insert_node(2);
+    let three = graph.insert_node(3);
+    let four = graph.insert_node(4);
+
+    graph.insert_edge(one, two, 2.0);
+    graph.insert_edge(two, three, 3.0);
+    graph.insert_edge(three, four, 4.0);
+    graph.insert_edge(four, one, 1.0);
+
+    assert_eq!(graph.remove_node(two), 2);
+    assert_eq!(graph.node_count(), 3);
+    assert!(graph.contains_node(&2).is_none());
+
+    // following ids are shifted down by one
+    let (three, four) = (two, three);
+    assert_eq!(graph.node(one), Some(&1));
+    assert_eq!(graph.node(three), Some(&3));
+    assert_eq!(graph.node(four), Some(&4));
+    assert_eq!(graph.node_ids().count(), 3);
+
+    let three_four = graph.contains_edge(three, four).unwrap();
+    let four_one = graph.contains_edge(four, one).unwrap();
+    assert_eq!(graph.weight(three_four), Some(&4.0));
+    assert_eq!(graph.weight(four_one), Some(&1.0));
+    assert!(graph.contains_edge(one, three).is_none());
+
+    let edges = graph
+        .edge_ids()
+        .map(|edge_id| graph.weight(edge_id))
+        .collect::<Vec<_>>();
+    assert_eq!(graph.edge_count(), 2);
+    assert!(!edges.contains(&Some(&2.0)));
+    assert!(!edges.contains(&Some(&3.0)));
+}
+
+pub fn graph_reserve<G: Graph<usize, f32>>() {
+    let mut graph = G::with_nodes([1, 2, 3, 4]);
+
+    graph.reserve_nodes(10);
+    graph.reserve_edges(10);
+
+    assert_ge!(graph.nodes_capacity(), 14);
+    assert_ge!(graph.edges_capacity(), 10);
+}
+
+pub fn graph_parallel_edges<G: Graph<usize, f32>>() {
+    let mut graph = G::with_capacity(2, 2);
+    let one = graph.insert_node(1);
+    let two = graph.insert_node(2);
+
+    let first = graph.insert_edge(one, two, 1.0);
+    let second = graph.insert_edge(one, two, 2.0);
+
+    assert_ne!(first, second);
+    assert_eq!(graph.edge_count(), 2);
+    assert_eq!(graph.weight(first), Some(&1.0));
+    assert_eq!(graph.weight(second), Some(&2.0));
+    assert_eq!(graph.contains_edge(one, two), Some(first));
+    assert_eq!(graph.weight(second.any()), Some(&1.0));
+
+    *graph.weight_mut(second).unwrap() = 3.0;
+    assert_eq!(graph.weight(first), Some(&1.0));
+
+    assert_eq!(graph.remove_edge(first), Some(1.0));
+    assert_eq!(graph.remove_edge(first), None);
+    assert_eq!(graph.edge_count(), 1);
+    assert_eq!(graph.weight(second), Some(&3.0));
+    assert_eq!(graph.contains_edge(one, two), Some(second));
+
+    if G::directed() {
+        assert!(graph.contains_edge(two, one).is_none());
+    } else {
+        assert_eq!(graph.contains_edge(two, one), Some(second.rev()));
+        assert_eq!(graph.remove_edge(second), Some(3.0));
+        assert!(graph.contains_edge(two, one).is_none());
+        assert!(graph.edges_empty());
+    }
+}
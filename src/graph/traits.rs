@@ -32,6 +32,12 @@ pub trait Count {
     fn node_count(&self) -> usize;
     fn edge_count(&self) -> usize;
 
+    /// Upper bound of [NodeId::as_usize] for all nodes in the graph.
+    /// Use this instead of the node count to size buffers indexed by node ids.
+    fn node_bound(&self) -> usize {
+        self.node_count()
+    }
+
     fn nodes_empty(&self) -> bool {
         self.node_count() == 0
     }
@@ -249,7 +255,13 @@ pub trait Insert: Base {
 }
 
 pub trait Remove: Base {
+    /// Removes the node together with all edges from and to it.
+    /// Compact graphs shift the ids of all following nodes down by one,
+    /// like [Vec::remove](std::vec::Vec::remove). Stable graphs keep all other ids valid.
     fn remove_node(&mut self, node_id: NodeId<Self::Id>) -> Self::Node;
+
+    /// Removes the edge and returns its weight.
+    /// In undirected graphs the reverse edge is removed as well.
     fn remove_edge(&mut self, edge_id: EdgeId<Self::Id>) -> Option<Self::Weight>;
 }
 
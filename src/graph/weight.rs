@@ -34,6 +34,30 @@ impl Sortable for f32 {
     }
 }
 
+/// Comparisons which allow for the rounding errors of floats
+pub trait Tolerance: PartialOrd + Sized {
+    /// `self <= other` up to rounding errors
+    fn le_approx(&self, other: &Self) -> bool;
+}
+
+default impl<T: PartialOrd> Tolerance for T {
+    default fn le_approx(&self, other: &Self) -> bool {
+        self <= other
+    }
+}
+
+impl Tolerance for f64 {
+    fn le_approx(&self, other: &Self) -> bool {
+        *self <= *other + 1e-9 * self.abs().max(other.abs()).max(1.0)
+    }
+}
+
+impl Tolerance for f32 {
+    fn le_approx(&self, other: &Self) -> bool {
+        *self <= *other + 1e-5 * self.abs().max(other.abs()).max(1.0)
+    }
+}
+
 pub trait Maximum {
     const MAX: Self;
 }
@@ -52,6 +76,7 @@ impl Maximum for u32 {
 
 pub trait Cost:
     Sortable
+    + Tolerance
     + Maximum
     + Default
     + Add<Self, Output = Self>
@@ -66,6 +91,7 @@ pub trait Cost:
 
 impl<
         T: Sortable
+            + Tolerance
             + Maximum
             + Default
             + Add<T, Output = T>
@@ -95,11 +121,6 @@ pub trait EdgeCost {
     fn cost_mut(&mut self) -> &mut Self::Cost;
 }
 
-pub trait EdgeDirection {
-    fn is_reverse(&self) -> bool;
-    fn reverse(&mut self);
-}
-
 pub trait EdgeFlow {
     type Flow;
 
@@ -108,11 +129,11 @@ pub trait EdgeFlow {
 }
 
 #[derive(Default, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FlowWeight<W> {
     pub flow: W,
     pub capacity: W,
     pub cost: W,
-    pub rev: bool,
 }
 
 impl<W> FlowWeight<W> {
@@ -120,16 +141,6 @@ impl<W> FlowWeight<W> {
         Self {
             capacity,
             cost,
-            rev: false,
-            flow,
-        }
-    }
-
-    pub fn rev(capacity: W, cost: W, flow: W) -> Self {
-        Self {
-            capacity,
-            cost,
-            rev: true,
             flow,
         }
     }
@@ -147,16 +158,6 @@ impl<W> EdgeFlow for FlowWeight<W> {
     }
 }
 
-impl<W> EdgeDirection for FlowWeight<W> {
-    fn is_reverse(&self) -> bool {
-        self.rev
-    }
-
-    fn reverse(&mut self) {
-        self.rev = !self.rev;
-    }
-}
-
 impl<W> EdgeCapacity for FlowWeight<W> {
     type Capacity = W;
 
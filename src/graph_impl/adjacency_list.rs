@@ -1,5 +1,6 @@
 use crate::{
     edge_list::EdgeList,
+    error::{GraphError, GraphResult},
     graph::{
         Base, Capacity, Clear, Contains, Count, Create, Directed, Extend, Get, GetMut, Graph,
         Index, IndexAdjacent, Insert, Iter, IterAdjacent, IterAdjacentMut, IterMut, Remove,
@@ -12,22 +13,53 @@ use std::fmt::Debug;
 type RawNodeId = NodeId<usize>;
 type RawEdgeId = EdgeId<usize>;
 
+/// Adjacency list which stores every edge in the list of its source node.
+/// With `MULTI` every inserted edge gets a unique key, so parallel edges can be told apart.
 #[derive(Debug, Clone)]
-pub struct AdjacencyList<Node, Weight, const DI: bool = false> {
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AdjacencyList<Node, Weight, const DI: bool = false, const MULTI: bool = false> {
     pub(crate) nodes: Vec<Node>,
     pub(crate) edges: Vec<Vec<Edge<usize, Weight>>>,
+    pub(crate) next_key: usize,
 }
 
-impl<Node, Weight: Clone, const DI: bool> AdjacencyList<Node, Weight, DI> {
+/// Adjacency list in which parallel edges have unique ids.
+pub type MultiAdjacencyList<Node, Weight, const DI: bool = false> =
+    AdjacencyList<Node, Weight, DI, true>;
+
+impl<Node, Weight: Clone, const DI: bool, const MULTI: bool>
+    AdjacencyList<Node, Weight, DI, MULTI>
+{
     pub fn new() -> Self {
         Self::with_capacity(0, 0)
     }
 }
 
-impl<Node, Weight: Copy, const DI: bool> From<EdgeList<Node, Weight, DI>>
-    for AdjacencyList<Node, Weight, DI>
+impl<Node, Weight, const DI: bool, const MULTI: bool> AdjacencyList<Node, Weight, DI, MULTI> {
+    /// Returns the ids of all edges from `from` to `to`.
+    pub fn edge_ids_between<'a>(
+        &'a self,
+        from: RawNodeId,
+        to: RawNodeId,
+    ) -> impl Iterator<Item = RawEdgeId> + 'a {
+        self.edges[from.as_usize()]
+            .iter()
+            .filter(move |edge| edge.to() == to)
+            .map(|edge| edge.edge_id)
+    }
+}
+
+/// Without `MULTI` parallel edges fail with [GraphError::EdgeAlreadyExists].
+impl<Node, Weight: Copy, const DI: bool, const MULTI: bool> TryFrom<EdgeList<Node, Weight, DI>>
+    for AdjacencyList<Node, Weight, DI, MULTI>
 {
-    fn from(edge_list: EdgeList<Node, Weight, DI>) -> Self {
+    type Error = GraphError;
+
+    fn try_from(edge_list: EdgeList<Node, Weight, DI>) -> GraphResult<Self> {
+        if !MULTI {
+            edge_list.ensure_simple()?;
+        }
+
         let EdgeList {
             nodes,
             edges,
@@ -39,30 +71,34 @@ impl<Node, Weight: Copy, const DI: bool> From<EdgeList<Node, Weight, DI>>
         for (from, to, weight) in edges.into_iter() {
             let from = NodeId::new_unchecked(from);
             let to = NodeId::new_unchecked(to);
-
-            if !DI {
-                adj_list.insert_edge(to, from, weight);
-            }
-
             adj_list.insert_edge(from, to, weight);
         }
 
-        adj_list
+        Ok(adj_list)
+    }
+}
+
+impl<Node: Clone, Weight: Clone, const DI: bool, const MULTI: bool>
+    From<&AdjacencyList<Node, Weight, DI, MULTI>> for EdgeList<Node, Weight, DI>
+{
+    fn from(adj_list: &AdjacencyList<Node, Weight, DI, MULTI>) -> Self {
+        EdgeList::from_graph(adj_list)
     }
 }
 
-impl<Node, Weight, const DI: bool> Base for AdjacencyList<Node, Weight, DI> {
+impl<Node, Weight, const DI: bool, const MULTI: bool> Base
+    for AdjacencyList<Node, Weight, DI, MULTI>
+{
     type Id = usize;
     type Node = Node;
     type Weight = Weight;
 }
 
-impl<Node, Weight, const DI: bool> Capacity for AdjacencyList<Node, Weight, DI> {
+impl<Node, Weight, const DI: bool, const MULTI: bool> Capacity
+    for AdjacencyList<Node, Weight, DI, MULTI>
+{
     fn edges_capacity(&self) -> usize {
-        self.edges
-            .first()
-            .map(|first| first.capacity())
-            .unwrap_or_default()
+        self.edges.iter().map(|adj| adj.capacity()).sum()
     }
 
     fn nodes_capacity(&self) -> usize {
@@ -70,7 +106,9 @@ impl<Node, Weight, const DI: bool> Capacity for AdjacencyList<Node, Weight, DI>
     }
 }
 
-impl<Node, Weight, const DI: bool> Clear for AdjacencyList<Node, Weight, DI> {
+impl<Node, Weight, const DI: bool, const MULTI: bool> Clear
+    for AdjacencyList<Node, Weight, DI, MULTI>
+{
     fn clear(&mut self) {
         self.nodes.clear();
         self.clear_edges();
@@ -83,7 +121,9 @@ impl<Node, Weight, const DI: bool> Clear for AdjacencyList<Node, Weight, DI> {
     }
 }
 
-impl<Node: PartialEq, Weight, const DI: bool> Contains for AdjacencyList<Node, Weight, DI> {
+impl<Node: PartialEq, Weight, const DI: bool, const MULTI: bool> Contains
+    for AdjacencyList<Node, Weight, DI, MULTI>
+{
     fn contains_node(&self, node: &Node) -> Option<RawNodeId> {
         self.nodes
             .iter()
@@ -93,27 +133,27 @@ impl<Node: PartialEq, Weight, const DI: bool> Contains for AdjacencyList<Node, W
     }
 
     fn contains_edge(&self, from: RawNodeId, to: RawNodeId) -> Option<RawEdgeId> {
-        self.edges[from.as_usize()].iter().find_map(|edge| {
-            if edge.to() == to {
-                Some(edge.edge_id)
-            } else {
-                None
-            }
-        })
+        self.edge_ids_between(from, to).next()
     }
 }
 
-impl<Node, Weight, const DI: bool> Count for AdjacencyList<Node, Weight, DI> {
+impl<Node, Weight, const DI: bool, const MULTI: bool> Count
+    for AdjacencyList<Node, Weight, DI, MULTI>
+{
     fn edge_count(&self) -> usize {
-        let count = self.edges.iter().fold(0, |mut akku, edges| {
-            akku += edges.len();
-            akku
-        });
+        let (count, loops) = self
+            .edges
+            .iter()
+            .flatten()
+            .fold((0, 0), |(count, loops), edge| {
+                (count + 1, loops + usize::from(edge.from() == edge.to()))
+            });
 
+        // undirected edges are stored twice, except for self loops
         if DI {
             count
         } else {
-            count / 2
+            (count + loops) / 2
         }
     }
 
@@ -122,29 +162,43 @@ impl<Node, Weight, const DI: bool> Count for AdjacencyList<Node, Weight, DI> {
     }
 }
 
-impl<Node, Weight: Clone, const DI: bool> Create for AdjacencyList<Node, Weight, DI> {
+impl<Node, Weight: Clone, const DI: bool, const MULTI: bool> Create
+    for AdjacencyList<Node, Weight, DI, MULTI>
+{
     fn with_capacity(nodes: usize, edges: usize) -> Self {
         let nodes = Vec::with_capacity(nodes);
         let edges = Vec::with_capacity(nodes.len());
 
-        Self { nodes, edges }
+        Self {
+            nodes,
+            edges,
+            next_key: 0,
+        }
     }
 
     fn with_nodes(nodes: impl IntoIterator<Item = Node>) -> Self {
         let nodes = nodes.into_iter().collect::<Vec<_>>();
         let edges = vec![Vec::new(); nodes.len()];
 
-        Self { nodes, edges }
+        Self {
+            nodes,
+            edges,
+            next_key: 0,
+        }
     }
 }
 
-impl<Node, Weight, const DI: bool> Directed for AdjacencyList<Node, Weight, DI> {
+impl<Node, Weight, const DI: bool, const MULTI: bool> Directed
+    for AdjacencyList<Node, Weight, DI, MULTI>
+{
     fn directed() -> bool {
         DI
     }
 }
 
-impl<Node, Weight, const DI: bool> Extend for AdjacencyList<Node, Weight, DI> {
+impl<Node, Weight: Clone, const DI: bool, const MULTI: bool> Extend
+    for AdjacencyList<Node, Weight, DI, MULTI>
+{
     fn extend_edges(&mut self, edges: impl Iterator<Item = (RawNodeId, RawNodeId, Weight)>) {
         for (from, to, weight) in edges {
             self.insert_edge(from, to, weight);
@@ -158,7 +212,9 @@ impl<Node, Weight, const DI: bool> Extend for AdjacencyList<Node, Weight, DI> {
     }
 }
 
-impl<Node, Weight, const DI: bool> Get for AdjacencyList<Node, Weight, DI> {
+impl<Node, Weight, const DI: bool, const MULTI: bool> Get
+    for AdjacencyList<Node, Weight, DI, MULTI>
+{
     fn node(&self, node_id: RawNodeId) -> Option<&Node> {
         self.nodes.get(node_id.as_usize())
     }
@@ -166,7 +222,7 @@ impl<Node, Weight, const DI: bool> Get for AdjacencyList<Node, Weight, DI> {
         self.edges[edge_id.from().as_usize()]
             .iter()
             .find_map(|edge| {
-                if edge.to() == edge_id.to() {
+                if edge_id.matches(&edge.edge_id) {
                     Some(&edge.weight)
                 } else {
                     None
@@ -175,7 +231,9 @@ impl<Node, Weight, const DI: bool> Get for AdjacencyList<Node, Weight, DI> {
     }
 }
 
-impl<Node, Weight, const DI: bool> GetMut for AdjacencyList<Node, Weight, DI> {
+impl<Node, Weight, const DI: bool, const MULTI: bool> GetMut
+    for AdjacencyList<Node, Weight, DI, MULTI>
+{
     fn node_mut(&mut self, node_id: RawNodeId) -> Option<&mut Node> {
         self.nodes.get_mut(node_id.as_usize())
     }
@@ -183,7 +241,7 @@ impl<Node, Weight, const DI: bool> GetMut for AdjacencyList<Node, Weight, DI> {
         self.edges[edge_id.from().as_usize()]
             .iter_mut()
             .find_map(|edge| {
-                if edge.to() == edge_id.to() {
+                if edge_id.matches(&edge.edge_id) {
                     Some(&mut edge.weight)
                 } else {
                     None
@@ -192,7 +250,9 @@ impl<Node, Weight, const DI: bool> GetMut for AdjacencyList<Node, Weight, DI> {
     }
 }
 
-impl<Node, Weight, const DI: bool> Index for AdjacencyList<Node, Weight, DI> {
+impl<Node, Weight, const DI: bool, const MULTI: bool> Index
+    for AdjacencyList<Node, Weight, DI, MULTI>
+{
     type EdgeIds<'a> = impl Iterator<Item = RawEdgeId> + 'a
     where Self: 'a;
     type NodeIds<'a> = impl Iterator<Item = RawNodeId> + 'a
@@ -210,7 +270,9 @@ impl<Node, Weight, const DI: bool> Index for AdjacencyList<Node, Weight, DI> {
     }
 }
 
-impl<Node, Weight, const DI: bool> IndexAdjacent for AdjacencyList<Node, Weight, DI> {
+impl<Node, Weight, const DI: bool, const MULTI: bool> IndexAdjacent
+    for AdjacencyList<Node, Weight, DI, MULTI>
+{
     type AdjacentEdgeIds<'a> = impl Iterator<Item = RawEdgeId> + 'a
     where Self: 'a;
     type AdjacentNodeIds<'a> = impl Iterator<Item = RawNodeId> + 'a
@@ -226,7 +288,9 @@ impl<Node, Weight, const DI: bool> IndexAdjacent for AdjacencyList<Node, Weight,
     }
 }
 
-impl<Node, Weight, const DI: bool> Iter for AdjacencyList<Node, Weight, DI> {
+impl<Node, Weight, const DI: bool, const MULTI: bool> Iter
+    for AdjacencyList<Node, Weight, DI, MULTI>
+{
     type Nodes<'a> = impl Iterator<Item = &'a Node> + 'a
     where
         Node: 'a,
@@ -249,7 +313,9 @@ impl<Node, Weight, const DI: bool> Iter for AdjacencyList<Node, Weight, DI> {
             .map(Into::into)
     }
 }
-impl<Node, Weight, const DI: bool> IterMut for AdjacencyList<Node, Weight, DI> {
+impl<Node, Weight, const DI: bool, const MULTI: bool> IterMut
+    for AdjacencyList<Node, Weight, DI, MULTI>
+{
     type NodesMut<'a> = impl Iterator<Item = &'a mut Node> + 'a
     where
         Node: 'a,
@@ -273,7 +339,9 @@ impl<Node, Weight, const DI: bool> IterMut for AdjacencyList<Node, Weight, DI> {
     }
 }
 
-impl<Node, Weight, const DI: bool> IterAdjacent for AdjacencyList<Node, Weight, DI> {
+impl<Node, Weight, const DI: bool, const MULTI: bool> IterAdjacent
+    for AdjacencyList<Node, Weight, DI, MULTI>
+{
     type Nodes<'a> = impl Iterator<Item = &'a Node> + 'a
     where
         Node: 'a,
@@ -293,7 +361,9 @@ impl<Node, Weight, const DI: bool> IterAdjacent for AdjacencyList<Node, Weight,
         self.edges[node_id.as_usize()].iter().map(Into::into)
     }
 }
-impl<Node, Weight, const DI: bool> IterAdjacentMut for AdjacencyList<Node, Weight, DI> {
+impl<Node, Weight, const DI: bool, const MULTI: bool> IterAdjacentMut
+    for AdjacencyList<Node, Weight, DI, MULTI>
+{
     type NodesMut<'a> = impl Iterator<Item = &'a mut Node> + 'a
     where
         Node: 'a,
@@ -323,7 +393,9 @@ impl<Node, Weight, const DI: bool> IterAdjacentMut for AdjacencyList<Node, Weigh
     }
 }
 
-impl<Node, Weight, const DI: bool> Insert for AdjacencyList<Node, Weight, DI> {
+impl<Node, Weight: Clone, const DI: bool, const MULTI: bool> Insert
+    for AdjacencyList<Node, Weight, DI, MULTI>
+{
     fn insert_node(&mut self, node: Node) -> RawNodeId {
         let node_id = RawNodeId::new_unchecked(self.nodes.len());
         self.nodes.push(node);
@@ -331,35 +403,84 @@ impl<Node, Weight, const DI: bool> Insert for AdjacencyList<Node, Weight, DI> {
         node_id
     }
 
-    // TODO undirected ??
+    /// Undirected edges are inserted into the lists of both nodes, self loops only once.
+    /// In multigraphs both directions share the same key.
     fn insert_edge(&mut self, from: RawNodeId, to: RawNodeId, weight: Weight) -> RawEdgeId {
-        let edge_id = RawEdgeId::new_unchecked(from, to);
-        let edge = Edge::new(edge_id, weight);
-        self.edges[from.as_usize()].push(edge);
+        let key = MULTI.then_some(self.next_key);
+        let edge_id = RawEdgeId::with_key(from, to, key);
+        if MULTI {
+            self.next_key += 1;
+        }
+
+        if !DI && from != to {
+            let edge = Edge::new(edge_id.rev(), weight.clone());
+            self.edges[to.as_usize()].push(edge);
+        }
+
+        self.edges[from.as_usize()].push(Edge::new(edge_id, weight));
         edge_id
     }
 }
 
-impl<Node: Default, Weight, const DI: bool> Remove for AdjacencyList<Node, Weight, DI> {
+impl<Node, Weight, const DI: bool, const MULTI: bool> Remove
+    for AdjacencyList<Node, Weight, DI, MULTI>
+{
     fn remove_node(&mut self, node_id: RawNodeId) -> Node {
-        self.edges[node_id.as_usize()].clear();
+        let index = node_id.as_usize();
+        let shift = |node_id: RawNodeId| {
+            if node_id.as_usize() > index {
+                RawNodeId::new_unchecked(node_id.as_usize() - 1)
+            } else {
+                node_id
+            }
+        };
+
+        self.edges.remove(index);
 
         for adj in self.edges.iter_mut() {
             adj.retain(|edge| edge.to() != node_id);
+
+            for edge in adj.iter_mut() {
+                edge.edge_id =
+                    RawEdgeId::with_key(shift(edge.from()), shift(edge.to()), edge.edge_id.key());
+            }
         }
 
-        std::mem::replace(&mut self.nodes[node_id.as_usize()], Node::default())
+        self.nodes.remove(index)
     }
 
     fn remove_edge(&mut self, edge_id: RawEdgeId) -> Option<Weight> {
-        // self.edges[edge_id.from().as_usize()].
-        todo!()
+        let adj = self.edges.get_mut(edge_id.from().as_usize())?;
+        let position = adj.iter().position(|edge| edge_id.matches(&edge.edge_id))?;
+        let edge = adj.remove(position);
+
+        // a multigraph knows the exact reverse edge by its key
+        if !DI && edge_id.from() != edge_id.to() {
+            let reverse = edge.edge_id.rev();
+            let adj = &mut self.edges[edge_id.to().as_usize()];
+            if let Some(position) = adj.iter().position(|edge| reverse.matches(&edge.edge_id)) {
+                adj.remove(position);
+            }
+        }
+
+        Some(edge.weight)
     }
 }
 
-impl<Node, Weight, const DI: bool> Reserve for AdjacencyList<Node, Weight, DI> {
+impl<Node, Weight, const DI: bool, const MULTI: bool> Reserve
+    for AdjacencyList<Node, Weight, DI, MULTI>
+{
+    /// Reserves the additional edges spread evenly over all adjacency lists.
     fn reserve_edges(&mut self, additional: usize) {
-        todo!()
+        let count = self.edges.len();
+        if count == 0 {
+            return;
+        }
+
+        let per_node = additional.div_ceil(count);
+        for adj in self.edges.iter_mut() {
+            adj.reserve(per_node);
+        }
     }
 
     fn reserve_nodes(&mut self, additional: usize) {
@@ -367,20 +488,20 @@ impl<Node, Weight, const DI: bool> Reserve for AdjacencyList<Node, Weight, DI> {
     }
 }
 
-impl<Node: crate::graph::Node, Weight: crate::graph::Weight, const DI: bool> Graph<Node, Weight>
-    for AdjacencyList<Node, Weight, DI>
+impl<Node: crate::graph::Node, Weight: crate::graph::Weight, const DI: bool, const MULTI: bool>
+    Graph<Node, Weight> for AdjacencyList<Node, Weight, DI, MULTI>
 {
 }
 
-impl<Node: crate::graph::Node, const DI: bool> WeightlessGraph<Node>
-    for AdjacencyList<Node, (), DI>
+impl<Node: crate::graph::Node, const DI: bool, const MULTI: bool> WeightlessGraph<Node>
+    for AdjacencyList<Node, (), DI, MULTI>
 {
 }
 
 #[cfg(test)]
 mod test {
     extern crate test;
-    use super::AdjacencyList;
+    use super::{AdjacencyList, MultiAdjacencyList};
     use crate::graph::test::*;
 
     #[test]
@@ -417,4 +538,88 @@ mod test {
     pub fn adj_list_index_adjacent() {
         graph_index_adjacent::<AdjacencyList<_, _>>()
     }
+
+    #[test]
+    pub fn adj_list_remove_edge() {
+        graph_remove_edge::<AdjacencyList<_, _>>()
+    }
+
+    #[test]
+    pub fn adj_list_remove_edge_directed() {
+        graph_remove_edge::<AdjacencyList<_, _, true>>()
+    }
+
+    #[test]
+    pub fn adj_list_remove_node() {
+        graph_remove_node::<AdjacencyList<_, _>>()
+    }
+
+    #[test]
+    pub fn adj_list_remove_node_directed() {
+        graph_remove_node::<AdjacencyList<_, _, true>>()
+    }
+
+    #[test]
+    pub fn adj_list_reserve() {
+        graph_reserve::<AdjacencyList<_, _>>()
+    }
+
+    #[test]
+    pub fn multi_adj_list_parallel_edges() {
+        graph_parallel_edges::<MultiAdjacencyList<_, _>>()
+    }
+
+    #[test]
+    pub fn multi_adj_list_parallel_edges_directed() {
+        graph_parallel_edges::<MultiAdjacencyList<_, _, true>>()
+    }
+
+    #[test]
+    pub fn adj_list_undirected_storage() {
+        use crate::{
+            graph::{Count, Create, Insert, Remove},
+            test::id,
+        };
+
+        fn undirected_storage<const MULTI: bool>() {
+            let mut graph = AdjacencyList::<usize, f64, false, MULTI>::with_nodes(0..2);
+            let edge = graph.insert_edge(id(1), id(0), 1.0);
+            let self_loop = graph.insert_edge(id(1), id(1), 2.0);
+
+            // both directions are stored, the self loop only once
+            assert_eq!(graph.edges[0].len(), 1);
+            assert_eq!(graph.edges[1].len(), 2);
+            assert_eq!(graph.edge_count(), 2);
+
+            assert_eq!(graph.remove_edge(self_loop), Some(2.0));
+            assert_eq!(graph.remove_edge(edge), Some(1.0));
+            assert_eq!(graph.edge_count(), 0);
+        }
+
+        undirected_storage::<false>();
+        undirected_storage::<true>();
+    }
+
+    #[test]
+    pub fn multi_adj_list_remove_node_directed() {
+        graph_remove_node::<MultiAdjacencyList<_, _, true>>()
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    pub fn adj_list_serde_round_trip() {
+        use crate::{
+            algorithms::cycle_canceling,
+            graph::{BalancedNode, FlowWeight},
+            test::bgraph,
+        };
+
+        let graph: AdjacencyList<BalancedNode<usize, f64>, FlowWeight<f64>, true> =
+            bgraph("data/Kostenminimal1.txt").unwrap();
+        let json = serde_json::to_string(&graph).unwrap();
+        let graph: AdjacencyList<BalancedNode<usize, f64>, FlowWeight<f64>, true> =
+            serde_json::from_str(&json).unwrap();
+
+        assert_eq!(cycle_canceling(&graph), 3.0);
+    }
 }
@@ -1,25 +1,129 @@
 use crate::{
     edge_list::EdgeList,
+    error::{GraphError, GraphResult},
     graph::{
         Base, Capacity, Clear, Contains, Count, Create, Directed, Extend, Get, GetMut, Graph,
         Index, IndexAdjacent, Insert, Iter, IterAdjacent, IterAdjacentMut, IterMut, Remove,
         Reserve,
     },
     prelude::{EdgeId, EdgeRef, EdgeRefMut, NodeId, WeightlessGraph},
-    structures::SparseMatrix,
 };
 
 type RawNodeId = NodeId<usize>;
 type RawEdgeId = EdgeId<usize>;
 
+/// Dense adjacency matrix, the weight of the edge from `i` to `j` is stored at `i * dim + j`.
+/// The side length `dim` grows by doubling, so inserting nodes is amortized.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(try_from = "RawAdjacencyMatrix<Node, Weight>")
+)]
 pub struct AdjacencyMatrix<Node, Weight, const DI: bool = false> {
     pub(crate) nodes: Vec<Node>,
-    pub(crate) edges: SparseMatrix<Weight>,
+    pub(crate) edges: Vec<Option<Weight>>,
+    pub(crate) dim: usize,
+    pub(crate) entries: usize,
 }
 
-impl<Node, W: Copy, const DI: bool> From<EdgeList<Node, W, DI>> for AdjacencyMatrix<Node, W, DI> {
-    fn from(edge_list: EdgeList<Node, W, DI>) -> Self {
+/// The deserialized fields of an [AdjacencyMatrix], checked before they are used
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct RawAdjacencyMatrix<Node, Weight> {
+    nodes: Vec<Node>,
+    edges: Vec<Option<Weight>>,
+    dim: usize,
+    entries: usize,
+}
+
+#[cfg(feature = "serde")]
+impl<Node, Weight, const DI: bool> TryFrom<RawAdjacencyMatrix<Node, Weight>>
+    for AdjacencyMatrix<Node, Weight, DI>
+{
+    type Error = &'static str;
+
+    fn try_from(raw: RawAdjacencyMatrix<Node, Weight>) -> Result<Self, Self::Error> {
+        let RawAdjacencyMatrix {
+            nodes,
+            edges,
+            dim,
+            entries,
+        } = raw;
+
+        if dim.checked_mul(dim) != Some(edges.len()) || nodes.len() > dim {
+            return Err("the matrix does not hold dim * dim cells for its nodes");
+        }
+
+        // cells outside of the nodes are empty, the others are counted by `entries`
+        let outside = edges.chunks(dim.max(1)).enumerate().any(|(from, row)| {
+            row.iter()
+                .enumerate()
+                .any(|(to, cell)| cell.is_some() && (from >= nodes.len() || to >= nodes.len()))
+        });
+        if outside || edges.iter().flatten().count() != entries {
+            return Err("the edges of the matrix are out of place");
+        }
+
+        Ok(Self {
+            nodes,
+            edges,
+            dim,
+            entries,
+        })
+    }
+}
+
+impl<Node, Weight, const DI: bool> AdjacencyMatrix<Node, Weight, DI> {
+    fn index(&self, from: RawNodeId, to: RawNodeId) -> Option<usize> {
+        let (from, to) = (from.as_usize(), to.as_usize());
+        if from < self.nodes.len() && to < self.nodes.len() {
+            Some(from * self.dim + to)
+        } else {
+            None
+        }
+    }
+
+    fn row(&self, node_id: RawNodeId) -> &[Option<Weight>] {
+        let start = node_id.as_usize() * self.dim;
+        &self.edges[start..start + self.nodes.len()]
+    }
+
+    fn row_mut(&mut self, node_id: RawNodeId) -> &mut [Option<Weight>] {
+        let start = node_id.as_usize() * self.dim;
+        &mut self.edges[start..start + self.nodes.len()]
+    }
+
+    /// Grows the matrix so that it can hold at least `count` nodes.
+    fn grow(&mut self, count: usize) {
+        if count <= self.dim {
+            return;
+        }
+
+        let dim = count.max(2 * self.dim);
+        let mut edges = Vec::with_capacity(dim * dim);
+        edges.resize_with(dim * dim, || None);
+
+        for from in 0..self.nodes.len() {
+            for to in 0..self.nodes.len() {
+                edges[from * dim + to] = self.edges[from * self.dim + to].take();
+            }
+        }
+
+        self.edges = edges;
+        self.dim = dim;
+    }
+}
+
+/// Parallel edges fail with [GraphError::EdgeAlreadyExists], a cell holds one weight.
+impl<Node, W: Copy, const DI: bool> TryFrom<EdgeList<Node, W, DI>>
+    for AdjacencyMatrix<Node, W, DI>
+{
+    type Error = GraphError;
+
+    fn try_from(edge_list: EdgeList<Node, W, DI>) -> GraphResult<Self> {
+        edge_list.ensure_simple()?;
+
         let EdgeList {
             nodes,
             edges,
@@ -31,15 +135,18 @@ impl<Node, W: Copy, const DI: bool> From<EdgeList<Node, W, DI>> for AdjacencyMat
         for (from, to, weight) in edges.into_iter() {
             let from = NodeId::new_unchecked(from);
             let to = NodeId::new_unchecked(to);
-
-            if !DI {
-                adj_mat.insert_edge(to, from, weight);
-            }
-
             adj_mat.insert_edge(from, to, weight);
         }
 
-        adj_mat
+        Ok(adj_mat)
+    }
+}
+
+impl<Node: Clone, W: Clone, const DI: bool> From<&AdjacencyMatrix<Node, W, DI>>
+    for EdgeList<Node, W, DI>
+{
+    fn from(adj_mat: &AdjacencyMatrix<Node, W, DI>) -> Self {
+        EdgeList::from_graph(adj_mat)
     }
 }
 
@@ -51,7 +158,7 @@ impl<Node, Weight, const DI: bool> Base for AdjacencyMatrix<Node, Weight, DI> {
 
 impl<Node, Weight, const DI: bool> Capacity for AdjacencyMatrix<Node, Weight, DI> {
     fn edges_capacity(&self) -> usize {
-        self.edges.row_count() * self.edges.col_count()
+        self.dim * self.dim
     }
 
     fn nodes_capacity(&self) -> usize {
@@ -61,12 +168,15 @@ impl<Node, Weight, const DI: bool> Capacity for AdjacencyMatrix<Node, Weight, DI
 
 impl<Node, Weight, const DI: bool> Clear for AdjacencyMatrix<Node, Weight, DI> {
     fn clear(&mut self) {
+        self.clear_edges();
         self.nodes.clear();
-        self.edges.clear();
     }
 
     fn clear_edges(&mut self) {
-        self.edges.clear();
+        for weight in self.edges.iter_mut() {
+            *weight = None;
+        }
+        self.entries = 0;
     }
 }
 
@@ -91,7 +201,15 @@ impl<Node: PartialEq, Weight, const DI: bool> Contains for AdjacencyMatrix<Node,
 
 impl<Node, Weight, const DI: bool> Count for AdjacencyMatrix<Node, Weight, DI> {
     fn edge_count(&self) -> usize {
-        self.edges.nnz()
+        if DI {
+            return self.entries;
+        }
+
+        // undirected edges fill both cells, except for self loops on the diagonal
+        let loops = (0..self.nodes.len())
+            .filter(|&node| self.edges[node * self.dim + node].is_some())
+            .count();
+        (self.entries + loops) / 2
     }
 
     fn node_count(&self) -> usize {
@@ -101,18 +219,27 @@ impl<Node, Weight, const DI: bool> Count for AdjacencyMatrix<Node, Weight, DI> {
 
 impl<Node, Weight, const DI: bool> Create for AdjacencyMatrix<Node, Weight, DI> {
     fn with_capacity(nodes: usize, _edges: usize) -> Self {
-        let edges = SparseMatrix::with_capacity(nodes, nodes);
-        let nodes = Vec::with_capacity(nodes);
-
-        Self { nodes, edges }
+        let mut adj_mat = Self {
+            nodes: Vec::with_capacity(nodes),
+            edges: Vec::new(),
+            dim: 0,
+            entries: 0,
+        };
+        adj_mat.grow(nodes);
+        adj_mat
     }
 
     fn with_nodes(nodes: impl IntoIterator<Item = Node>) -> Self {
         let nodes: Vec<Node> = nodes.into_iter().collect();
-        let node_count = nodes.len();
-        let edges = SparseMatrix::with_capacity(node_count, node_count);
-
-        Self { nodes, edges }
+        let mut adj_mat = Self {
+            nodes: Vec::new(),
+            edges: Vec::new(),
+            dim: 0,
+            entries: 0,
+        };
+        adj_mat.grow(nodes.len());
+        adj_mat.nodes = nodes;
+        adj_mat
     }
 }
 
@@ -122,15 +249,17 @@ impl<Node, Weight, const DI: bool> Directed for AdjacencyMatrix<Node, Weight, DI
     }
 }
 
-impl<Node, Weight, const DI: bool> Extend for AdjacencyMatrix<Node, Weight, DI> {
+impl<Node, Weight: Clone, const DI: bool> Extend for AdjacencyMatrix<Node, Weight, DI> {
     fn extend_edges(&mut self, edges: impl Iterator<Item = (RawNodeId, RawNodeId, Weight)>) {
         for (from, to, weight) in edges {
-            self.edges.insert(from.as_usize(), to.as_usize(), weight)
+            self.insert_edge(from, to, weight);
         }
     }
 
     fn extend_nodes(&mut self, nodes: impl Iterator<Item = Node>) {
-        self.nodes.extend(nodes);
+        for node in nodes {
+            self.insert_node(node);
+        }
     }
 }
 
@@ -138,9 +267,10 @@ impl<Node, Weight, const DI: bool> Get for AdjacencyMatrix<Node, Weight, DI> {
     fn node(&self, node_id: RawNodeId) -> Option<&Node> {
         self.nodes.get(node_id.as_usize())
     }
+
     fn weight(&self, edge_id: RawEdgeId) -> Option<&Weight> {
-        self.edges
-            .get(edge_id.from().as_usize(), edge_id.to().as_usize())
+        let index = self.index(edge_id.from(), edge_id.to())?;
+        self.edges[index].as_ref()
     }
 }
 
@@ -148,20 +278,39 @@ impl<Node, Weight, const DI: bool> GetMut for AdjacencyMatrix<Node, Weight, DI>
     fn node_mut(&mut self, node_id: RawNodeId) -> Option<&mut Node> {
         self.nodes.get_mut(node_id.as_usize())
     }
+
     fn weight_mut(&mut self, edge_id: RawEdgeId) -> Option<&mut Weight> {
-        self.edges
-            .get_mut(edge_id.from().as_usize(), edge_id.to().as_usize())
+        let index = self.index(edge_id.from(), edge_id.to())?;
+        self.edges[index].as_mut()
     }
 }
 
-impl<Node, Weight, const DI: bool> Insert for AdjacencyMatrix<Node, Weight, DI> {
+impl<Node, Weight: Clone, const DI: bool> Insert for AdjacencyMatrix<Node, Weight, DI> {
     fn insert_node(&mut self, node: Node) -> RawNodeId {
         let node_id = RawNodeId::new_unchecked(self.nodes.len());
+        self.grow(self.nodes.len() + 1);
         self.nodes.push(node);
-        return node_id;
+        node_id
     }
+
+    /// Replaces the weight if the edge already exists.
+    /// Undirected edges fill both cells.
     fn insert_edge(&mut self, from: RawNodeId, to: RawNodeId, weight: Weight) -> RawEdgeId {
-        self.edges.insert(from.as_usize(), to.as_usize(), weight);
+        let index = self
+            .index(from, to)
+            .expect("both nodes must be in the graph");
+
+        if !DI && from != to {
+            let index = self.index(to, from).unwrap();
+            if self.edges[index].replace(weight.clone()).is_none() {
+                self.entries += 1;
+            }
+        }
+
+        if self.edges[index].replace(weight).is_none() {
+            self.entries += 1;
+        }
+
         RawEdgeId::new_unchecked(from, to)
     }
 }
@@ -173,11 +322,8 @@ impl<Node, Weight, const DI: bool> Index for AdjacencyMatrix<Node, Weight, DI> {
     where Self: 'a;
 
     fn edge_ids<'a>(&'a self) -> Self::EdgeIds<'a> {
-        self.edges.iter().map(|(from, to, _)| {
-            let from = RawNodeId::new_unchecked(from);
-            let to = RawNodeId::new_unchecked(to);
-            RawEdgeId::new_unchecked(from, to)
-        })
+        self.node_ids()
+            .flat_map(|from| self.adjacent_edge_ids(from))
     }
 
     fn node_ids<'a>(&'a self) -> Self::NodeIds<'a> {
@@ -192,14 +338,15 @@ impl<Node, Weight, const DI: bool> IndexAdjacent for AdjacencyMatrix<Node, Weigh
     where Self: 'a;
 
     fn adjacent_edge_ids<'a>(&'a self, node_id: RawNodeId) -> Self::AdjacentEdgeIds<'a> {
-        self.edges.row(node_id.as_usize()).map(move |(to, _)| {
-            let to = RawNodeId::new_unchecked(to);
-            RawEdgeId::new_unchecked(node_id, to)
-        })
+        self.adjacent_node_ids(node_id)
+            .map(move |to| RawEdgeId::new_unchecked(node_id, to))
     }
+
     fn adjacent_node_ids<'a>(&'a self, node_id: RawNodeId) -> Self::AdjacentNodeIds<'a> {
-        self.edges
-            .row(node_id.as_usize())
+        self.row(node_id)
+            .iter()
+            .enumerate()
+            .filter(|(_, weight)| weight.is_some())
             .map(|(to, _)| RawNodeId::new_unchecked(to))
     }
 }
@@ -218,15 +365,13 @@ impl<Node, Weight, const DI: bool> Iter for AdjacencyMatrix<Node, Weight, DI> {
     fn iter_nodes<'a>(&'a self) -> Self::Nodes<'a> {
         self.nodes.iter()
     }
+
     fn iter_edges<'a>(&'a self) -> Self::Edges<'a> {
-        self.edges.iter().map(|(from, to, weight)| {
-            let from = RawNodeId::new_unchecked(from);
-            let to = RawNodeId::new_unchecked(to);
-            let edge_id = RawEdgeId::new_unchecked(from, to);
-            EdgeRef::new(edge_id, weight)
-        })
+        self.node_ids()
+            .flat_map(|from| self.iter_adjacent_edges(from))
     }
 }
+
 impl<Node, Weight, const DI: bool> IterMut for AdjacencyMatrix<Node, Weight, DI> {
     type NodesMut<'a> = impl Iterator<Item = &'a mut Node> + 'a
     where
@@ -243,12 +388,18 @@ impl<Node, Weight, const DI: bool> IterMut for AdjacencyMatrix<Node, Weight, DI>
     }
 
     fn iter_edges_mut<'a>(&'a mut self) -> Self::EdgesMut<'a> {
-        self.edges.iter_mut().map(|(from, to, weight)| {
-            let from = RawNodeId::new_unchecked(from);
-            let to = RawNodeId::new_unchecked(to);
-            let edge_id = RawEdgeId::new_unchecked(from, to);
-            EdgeRefMut::new(edge_id, weight)
-        })
+        let dim = self.dim;
+        self.edges
+            .iter_mut()
+            .enumerate()
+            .filter_map(move |(index, weight)| {
+                let from = RawNodeId::new_unchecked(index / dim);
+                let to = RawNodeId::new_unchecked(index % dim);
+                let edge_id = RawEdgeId::new_unchecked(from, to);
+                weight
+                    .as_mut()
+                    .map(|weight| EdgeRefMut::new(edge_id, weight))
+            })
     }
 }
 
@@ -269,13 +420,17 @@ impl<Node, Weight, const DI: bool> IterAdjacent for AdjacencyMatrix<Node, Weight
     }
 
     fn iter_adjacent_edges<'a>(&'a self, node_id: RawNodeId) -> Self::Edges<'a> {
-        self.edges.row(node_id.as_usize()).map(move |(to, weight)| {
-            let to = RawNodeId::new_unchecked(to);
-            let edge_id = RawEdgeId::new_unchecked(node_id, to);
-            EdgeRef::new(edge_id, weight)
-        })
+        self.row(node_id)
+            .iter()
+            .enumerate()
+            .filter_map(move |(to, weight)| {
+                let to = RawNodeId::new_unchecked(to);
+                let edge_id = RawEdgeId::new_unchecked(node_id, to);
+                weight.as_ref().map(|weight| EdgeRef::new(edge_id, weight))
+            })
     }
 }
+
 impl<Node, Weight, const DI: bool> IterAdjacentMut for AdjacencyMatrix<Node, Weight, DI> {
     type NodesMut<'a> = impl Iterator<Item = &'a mut Node> + 'a
     where
@@ -303,33 +458,68 @@ impl<Node, Weight, const DI: bool> IterAdjacentMut for AdjacencyMatrix<Node, Wei
     }
 
     fn iter_adjacent_edges_mut<'a>(&'a mut self, node_id: RawNodeId) -> Self::EdgesMut<'a> {
-        self.edges
-            .row_mut(node_id.as_usize())
-            .map(move |(to, weight)| {
+        self.row_mut(node_id)
+            .iter_mut()
+            .enumerate()
+            .filter_map(move |(to, weight)| {
                 let to = RawNodeId::new_unchecked(to);
                 let edge_id = RawEdgeId::new_unchecked(node_id, to);
-                EdgeRefMut::new(edge_id, weight)
+                weight
+                    .as_mut()
+                    .map(|weight| EdgeRefMut::new(edge_id, weight))
             })
     }
 }
 
-impl<Node: Default, Weight, const DI: bool> Remove for AdjacencyMatrix<Node, Weight, DI> {
+impl<Node, Weight, const DI: bool> Remove for AdjacencyMatrix<Node, Weight, DI> {
     fn remove_node(&mut self, node_id: RawNodeId) -> Node {
-        todo!()
+        let index = node_id.as_usize();
+        let count = self.nodes.len();
+        let dim = self.dim;
+
+        for other in 0..count {
+            for cell in [index * dim + other, other * dim + index] {
+                if self.edges[cell].take().is_some() {
+                    self.entries -= 1;
+                }
+            }
+        }
+
+        // every cell moves up and left, so its source is never overwritten before it is read
+        for from in 0..count - 1 {
+            for to in 0..count - 1 {
+                let source_from = if from >= index { from + 1 } else { from };
+                let source_to = if to >= index { to + 1 } else { to };
+                self.edges[from * dim + to] = self.edges[source_from * dim + source_to].take();
+            }
+        }
+
+        self.nodes.remove(index)
     }
 
     fn remove_edge(&mut self, edge_id: RawEdgeId) -> Option<Weight> {
-        todo!()
+        let index = self.index(edge_id.from(), edge_id.to())?;
+        let weight = self.edges[index].take()?;
+        self.entries -= 1;
+
+        if !DI && edge_id.from() != edge_id.to() {
+            let index = self.index(edge_id.to(), edge_id.from()).unwrap();
+            if self.edges[index].take().is_some() {
+                self.entries -= 1;
+            }
+        }
+
+        Some(weight)
     }
 }
 
 impl<Node, Weight, const DI: bool> Reserve for AdjacencyMatrix<Node, Weight, DI> {
-    fn reserve_edges(&mut self, additional: usize) {
-        todo!()
-    }
+    /// Every possible edge between the nodes already has its place in the matrix.
+    fn reserve_edges(&mut self, _additional: usize) {}
 
     fn reserve_nodes(&mut self, additional: usize) {
-        self.nodes.reserve(additional)
+        self.nodes.reserve(additional);
+        self.grow(self.nodes.len() + additional);
     }
 }
 
@@ -347,7 +537,10 @@ impl<Node: crate::graph::Node, const DI: bool> WeightlessGraph<Node>
 mod test {
     extern crate test;
     use super::AdjacencyMatrix;
-    use crate::graph::test::*;
+    use crate::{
+        graph::{test::*, Count, Create, Get, Index, Insert},
+        prelude::{AdjacencyList, EdgeId, EdgeList, NodeId},
+    };
 
     #[test]
     pub fn adj_mat_create_with_nodes() {
@@ -383,4 +576,84 @@ mod test {
     pub fn adj_mat_index_adjacent() {
         graph_index_adjacent::<AdjacencyMatrix<_, _>>()
     }
+
+    #[test]
+    pub fn adj_mat_remove_edge() {
+        graph_remove_edge::<AdjacencyMatrix<_, _>>()
+    }
+
+    #[test]
+    pub fn adj_mat_remove_edge_directed() {
+        graph_remove_edge::<AdjacencyMatrix<_, _, true>>()
+    }
+
+    #[test]
+    pub fn adj_mat_remove_node() {
+        graph_remove_node::<AdjacencyMatrix<_, _>>()
+    }
+
+    #[test]
+    pub fn adj_mat_remove_node_directed() {
+        graph_remove_node::<AdjacencyMatrix<_, _, true>>()
+    }
+
+    #[test]
+    pub fn adj_mat_reserve() {
+        graph_reserve::<AdjacencyMatrix<_, _>>()
+    }
+
+    #[test]
+    pub fn adj_mat_grow() {
+        let mut graph = AdjacencyMatrix::<usize, f32, true>::with_nodes([1, 2]);
+        let one = graph.node_ids().next().unwrap();
+        let two = graph.node_ids().last().unwrap();
+        let one_two = graph.insert_edge(one, two, 1.0);
+
+        let nodes = (3..20)
+            .map(|node| graph.insert_node(node))
+            .collect::<Vec<_>>();
+        let last = graph.insert_edge(nodes[16], one, 2.0);
+
+        assert_eq!(graph.weight(one_two), Some(&1.0));
+        assert_eq!(graph.weight(last), Some(&2.0));
+        assert_eq!(graph.edge_count(), 2);
+
+        // the matrix holds at most one edge per direction
+        graph.insert_edge(one, two, 3.0);
+        assert_eq!(graph.weight(one_two), Some(&3.0));
+        assert_eq!(graph.edge_count(), 2);
+    }
+
+    #[test]
+    pub fn adj_mat_edge_count_self_loop() {
+        let edges = [(0, 0, ()), (0, 1, ())];
+        let edge_list = EdgeList::<usize, ()>::with(edges.into_iter(), 2);
+
+        let adj_mat = AdjacencyMatrix::try_from(edge_list.clone()).unwrap();
+        let adj_list = AdjacencyList::<usize, ()>::try_from(edge_list).unwrap();
+
+        assert_eq!(adj_mat.edge_count(), 2);
+        assert_eq!(adj_mat.edge_count(), adj_list.edge_count());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    pub fn adj_mat_serde_rejects_invalid_cells() {
+        let mut graph = AdjacencyMatrix::<usize, f64, true>::with_nodes(0..3);
+        let (zero, two) = (NodeId::new_unchecked(0), NodeId::new_unchecked(2));
+        graph.insert_edge(zero, two, 1.0);
+
+        let json = serde_json::to_string(&graph).unwrap();
+        let parsed: AdjacencyMatrix<usize, f64, true> = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.weight(EdgeId::new_unchecked(zero, two)), Some(&1.0));
+
+        let json = r#"{"nodes":[0,1],"edges":[null,1.0,null],"dim":2,"entries":1}"#;
+        assert!(serde_json::from_str::<AdjacencyMatrix<usize, f64, true>>(json).is_err());
+
+        let json = r#"{"nodes":[0],"edges":[null,1.0,null,null],"dim":2,"entries":1}"#;
+        assert!(serde_json::from_str::<AdjacencyMatrix<usize, f64, true>>(json).is_err());
+
+        let json = r#"{"nodes":[0,1],"edges":[null,1.0,null,null],"dim":2,"entries":2}"#;
+        assert!(serde_json::from_str::<AdjacencyMatrix<usize, f64, true>>(json).is_err());
+    }
 }
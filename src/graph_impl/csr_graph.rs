@@ -0,0 +1,311 @@
+use crate::{
+    edge_list::EdgeList,
+    error::{GraphError, GraphResult},
+    graph::{Base, Count, Directed, Get, Index, IndexAdjacent, Iter, IterAdjacent},
+    prelude::{EdgeId, EdgeRef, NodeId},
+};
+
+type RawNodeId = NodeId<usize>;
+type RawEdgeId = EdgeId<usize>;
+
+/// Immutable graph in compressed sparse row format.
+/// The targets and weights of all edges leaving node `i`
+/// are stored at `offsets[i]..offsets[i + 1]` in flat arrays.
+#[derive(Debug, Clone)]
+pub struct CsrGraph<Node, Weight, const DI: bool = false> {
+    pub(crate) nodes: Vec<Node>,
+    pub(crate) offsets: Vec<usize>,
+    pub(crate) targets: Vec<RawNodeId>,
+    pub(crate) weights: Vec<Weight>,
+}
+
+impl<Node, Weight, const DI: bool> CsrGraph<Node, Weight, DI> {
+    /// Builds the graph from the edges leaving every node, the nodes are numbered in order.
+    pub(crate) fn with_adjacency(nodes: Vec<Node>, edges: Vec<(usize, usize, Weight)>) -> Self {
+        let mut offsets = vec![0; nodes.len() + 1];
+        for (from, _, _) in &edges {
+            offsets[from + 1] += 1;
+        }
+        for i in 0..nodes.len() {
+            offsets[i + 1] += offsets[i];
+        }
+
+        // a stable sort keeps the order of parallel edges
+        let mut edges = edges;
+        edges.sort_by_key(|(from, _, _)| *from);
+
+        let (targets, weights) = edges
+            .into_iter()
+            .map(|(_, to, weight)| (RawNodeId::new_unchecked(to), weight))
+            .unzip();
+
+        Self {
+            nodes,
+            offsets,
+            targets,
+            weights,
+        }
+    }
+
+    /// Copies any graph, gaps in its node ids are closed.
+    pub fn from_graph<G>(graph: &G) -> Self
+    where
+        Node: Clone,
+        Weight: Clone,
+        G: Index + Iter + IterAdjacent + Count + Base<Node = Node, Weight = Weight>,
+    {
+        let mut index = vec![None; graph.node_bound()];
+        for (i, node_id) in graph.node_ids().enumerate() {
+            index[node_id.as_usize()] = Some(i);
+        }
+
+        let nodes = graph.iter_nodes().cloned().collect();
+        let edges = graph
+            .node_ids()
+            .flat_map(|from| graph.iter_adjacent_edges(from))
+            .map(|edge| {
+                let from = index[edge.from().as_usize()].unwrap();
+                let to = index[edge.to().as_usize()].unwrap();
+                (from, to, edge.weight.clone())
+            })
+            .collect();
+
+        Self::with_adjacency(nodes, edges)
+    }
+
+    fn range(&self, node_id: RawNodeId) -> std::ops::Range<usize> {
+        let index = node_id.as_usize();
+        self.offsets[index]..self.offsets[index + 1]
+    }
+}
+
+/// Parallel edges fail with [GraphError::EdgeAlreadyExists].
+impl<Node, Weight, const DI: bool> TryFrom<EdgeList<Node, Weight, DI>>
+    for CsrGraph<Node, Weight, DI>
+where
+    Weight: Copy,
+{
+    type Error = GraphError;
+
+    fn try_from(edge_list: EdgeList<Node, Weight, DI>) -> GraphResult<Self> {
+        edge_list.ensure_simple()?;
+
+        let EdgeList {
+            nodes,
+            edges,
+            node_count: _,
+        } = edge_list;
+
+        let mut adjacency = Vec::with_capacity(if DI { edges.nnz() } else { 2 * edges.nnz() });
+        for (from, to, weight) in edges.into_iter() {
+            if !DI && from != to {
+                adjacency.push((to, from, weight));
+            }
+
+            adjacency.push((from, to, weight));
+        }
+
+        Ok(Self::with_adjacency(nodes, adjacency))
+    }
+}
+
+impl<Node, Weight, const DI: bool> Base for CsrGraph<Node, Weight, DI> {
+    type Id = usize;
+    type Node = Node;
+    type Weight = Weight;
+}
+
+impl<Node, Weight, const DI: bool> Count for CsrGraph<Node, Weight, DI> {
+    fn edge_count(&self) -> usize {
+        if DI {
+            return self.targets.len();
+        }
+
+        // undirected edges are stored twice, except for self loops
+        let loops = self
+            .node_ids()
+            .map(|from| self.adjacent_node_ids(from).filter(|&to| to == from).count())
+            .sum::<usize>();
+        (self.targets.len() + loops) / 2
+    }
+
+    fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+}
+
+impl<Node, Weight, const DI: bool> Directed for CsrGraph<Node, Weight, DI> {
+    fn directed() -> bool {
+        DI
+    }
+}
+
+impl<Node, Weight, const DI: bool> Get for CsrGraph<Node, Weight, DI> {
+    fn node(&self, node_id: RawNodeId) -> Option<&Node> {
+        self.nodes.get(node_id.as_usize())
+    }
+
+    fn weight(&self, edge_id: RawEdgeId) -> Option<&Weight> {
+        self.range(edge_id.from())
+            .find(|&i| self.targets[i] == edge_id.to())
+            .map(|i| &self.weights[i])
+    }
+}
+
+impl<Node, Weight, const DI: bool> Index for CsrGraph<Node, Weight, DI> {
+    type EdgeIds<'a> = impl Iterator<Item = RawEdgeId> + 'a
+    where Self: 'a;
+    type NodeIds<'a> = impl Iterator<Item = RawNodeId> + 'a
+    where Self: 'a;
+
+    fn edge_ids<'a>(&'a self) -> Self::EdgeIds<'a> {
+        self.node_ids()
+            .flat_map(|from| self.adjacent_edge_ids(from))
+    }
+
+    fn node_ids<'a>(&'a self) -> Self::NodeIds<'a> {
+        (0..self.nodes.len()).map(RawNodeId::new_unchecked)
+    }
+}
+
+impl<Node, Weight, const DI: bool> IndexAdjacent for CsrGraph<Node, Weight, DI> {
+    type AdjacentEdgeIds<'a> = impl Iterator<Item = RawEdgeId> + 'a
+    where Self: 'a;
+    type AdjacentNodeIds<'a> = impl Iterator<Item = RawNodeId> + 'a
+    where Self: 'a;
+
+    fn adjacent_edge_ids<'a>(&'a self, node_id: RawNodeId) -> Self::AdjacentEdgeIds<'a> {
+        self.adjacent_node_ids(node_id)
+            .map(move |to| RawEdgeId::new_unchecked(node_id, to))
+    }
+
+    fn adjacent_node_ids<'a>(&'a self, node_id: RawNodeId) -> Self::AdjacentNodeIds<'a> {
+        self.targets[self.range(node_id)].iter().copied()
+    }
+}
+
+impl<Node, Weight, const DI: bool> Iter for CsrGraph<Node, Weight, DI> {
+    type Nodes<'a> = impl Iterator<Item = &'a Node> + 'a
+    where
+        Node: 'a,
+        Self: 'a;
+
+    type Edges<'a> = impl Iterator<Item = EdgeRef<'a, usize, Weight>> + 'a
+    where
+        Weight: 'a,
+        Self: 'a;
+
+    fn iter_nodes<'a>(&'a self) -> Self::Nodes<'a> {
+        self.nodes.iter()
+    }
+
+    fn iter_edges<'a>(&'a self) -> Self::Edges<'a> {
+        self.node_ids()
+            .flat_map(|from| self.iter_adjacent_edges(from))
+    }
+}
+
+impl<Node, Weight, const DI: bool> IterAdjacent for CsrGraph<Node, Weight, DI> {
+    type Nodes<'a> = impl Iterator<Item = &'a Node> + 'a
+    where
+        Node: 'a,
+        Self: 'a;
+
+    type Edges<'a> = impl Iterator<Item = EdgeRef<'a, usize, Weight>> + 'a
+    where
+        Weight: 'a,
+        Self: 'a;
+
+    fn iter_adjacent_nodes<'a>(&'a self, node_id: RawNodeId) -> Self::Nodes<'a> {
+        self.adjacent_node_ids(node_id)
+            .map(|node_id| &self.nodes[node_id.as_usize()])
+    }
+
+    fn iter_adjacent_edges<'a>(&'a self, node_id: RawNodeId) -> Self::Edges<'a> {
+        self.range(node_id).map(move |i| {
+            let edge_id = RawEdgeId::new_unchecked(node_id, self.targets[i]);
+            EdgeRef::new(edge_id, &self.weights[i])
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    extern crate test;
+    use super::CsrGraph;
+    use crate::{
+        algorithms::{bfs_scc, dfs_scc, dijkstra_between, prim},
+        graph::{Count, Get, Index, IndexAdjacent, Remove},
+        prelude::*,
+        test::{digraph, id, undigraph, weightless_undigraph},
+    };
+
+    #[test]
+    fn csr_from_edge_list() {
+        let graph: CsrGraph<_, _, true> = digraph("data/Wege1.txt").unwrap();
+
+        assert_eq!(graph.node_count(), 8);
+        assert_eq!(graph.edge_count(), 13);
+        assert_eq!(
+            graph.weight(EdgeId::new_unchecked(id(3), id(5))),
+            Some(&4.0)
+        );
+        assert_eq!(graph.weight(EdgeId::new_unchecked(id(5), id(3))), None);
+
+        let mut adjacent = graph.adjacent_node_ids(id(6)).collect::<Vec<_>>();
+        adjacent.sort();
+        assert_eq!(adjacent, vec![id(3), id(5)]);
+    }
+
+    #[test]
+    fn csr_from_graph() {
+        let adj_list: AdjacencyList<_, _> = undigraph("data/G_1_2.txt").unwrap();
+        let graph = CsrGraph::<_, _>::from_graph(&adj_list);
+
+        assert_eq!(graph.node_count(), adj_list.node_count());
+        assert_eq!(graph.edge_count(), adj_list.edge_count());
+
+        for node_id in graph.node_ids() {
+            let adjacent = graph.adjacent_node_ids(node_id).collect::<Vec<_>>();
+            let expected = adj_list.adjacent_node_ids(node_id).collect::<Vec<_>>();
+            assert_eq!(adjacent, expected);
+        }
+    }
+
+    #[test]
+    fn csr_from_stable_graph() {
+        let mut stable: StableAdjacencyList<_, _, true> = digraph("data/Wege1.txt").unwrap();
+        let removed = stable.node_ids().nth(2).unwrap();
+        stable.remove_node(removed);
+
+        let graph = CsrGraph::<_, _, true>::from_graph(&stable);
+
+        assert_eq!(graph.node_count(), 7);
+        assert_eq!(graph.edge_count(), stable.edge_count());
+        assert!(graph.edge_ids().all(|edge_id| edge_id.to().as_usize() < 7));
+    }
+
+    #[test]
+    fn csr_scc() {
+        let graph: CsrGraph<_, _> = weightless_undigraph("data/Graph2.txt").unwrap();
+
+        assert_eq!(bfs_scc(&graph).len(), 4);
+        assert_eq!(dfs_scc(&graph).len(), 4);
+    }
+
+    #[test]
+    fn csr_dijkstra() {
+        let graph: CsrGraph<_, _, true> = digraph("data/Wege1.txt").unwrap();
+
+        let total = dijkstra_between(&graph, id(2), id(0)).unwrap();
+        assert_eq!(total, 6.0);
+    }
+
+    #[test]
+    fn csr_prim() {
+        let graph: CsrGraph<_, _> = undigraph("data/G_1_2.txt").unwrap();
+
+        let total = prim(&graph) as f32;
+        assert_eq!(total, 287.32286);
+    }
+}
@@ -1,7 +1,11 @@
 pub use adjacency_list::*;
 pub use adjacency_matrix::*;
+pub use csr_graph::*;
+pub use stable_adjacency_list::*;
 pub use tree::*;
 
 mod adjacency_list;
 mod adjacency_matrix;
+mod csr_graph;
+mod stable_adjacency_list;
 mod tree;
@@ -0,0 +1,681 @@
+use crate::{
+    edge_list::EdgeList,
+    error::{GraphError, GraphResult},
+    graph::{
+        Base, Capacity, Clear, Contains, Count, Create, Directed, Extend, Get, GetMut, Graph,
+        Index, IndexAdjacent, Insert, Iter, IterAdjacent, IterAdjacentMut, IterMut, Remove,
+        Reserve,
+    },
+    prelude::{Edge, EdgeId, EdgeRef, EdgeRefMut, NodeId, WeightlessGraph},
+};
+use std::fmt::Debug;
+
+/// Id of a node slot together with the generation of the slot.
+/// The generation is increased whenever the slot is freed,
+/// so ids of removed nodes never refer to nodes inserted later.
+#[derive(Hash, PartialEq, Eq, PartialOrd, Ord, Copy, Clone, Debug)]
+pub struct StableId {
+    index: usize,
+    generation: u32,
+}
+
+impl StableId {
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    pub fn generation(&self) -> u32 {
+        self.generation
+    }
+}
+
+impl From<StableId> for usize {
+    fn from(id: StableId) -> Self {
+        id.index
+    }
+}
+
+type StableNodeId = NodeId<StableId>;
+type StableEdgeId = EdgeId<StableId>;
+
+/// Adjacency list whose node and edge ids stay valid when other nodes are removed.
+/// Freed slots are reused by later insertions with a new generation.
+#[derive(Debug, Clone)]
+pub struct StableAdjacencyList<Node, Weight, const DI: bool = false> {
+    pub(crate) nodes: Vec<Option<Node>>,
+    pub(crate) generations: Vec<u32>,
+    pub(crate) edges: Vec<Vec<Edge<StableId, Weight>>>,
+    pub(crate) free: Vec<usize>,
+}
+
+impl<Node, Weight: Clone, const DI: bool> StableAdjacencyList<Node, Weight, DI> {
+    pub fn new() -> Self {
+        Self::with_capacity(0, 0)
+    }
+
+    /// Inserts the edge, `None` if one of the nodes was removed.
+    pub fn try_insert_edge(
+        &mut self,
+        from: StableNodeId,
+        to: StableNodeId,
+        weight: Weight,
+    ) -> Option<StableEdgeId> {
+        if !self.is_valid(from) || !self.is_valid(to) {
+            return None;
+        }
+
+        let edge_id = StableEdgeId::new_unchecked(from, to);
+        if !DI && from != to {
+            let edge = Edge::new(edge_id.rev(), weight.clone());
+            self.edges[to.as_usize()].push(edge);
+        }
+
+        let edge = Edge::new(edge_id, weight);
+        self.edges[from.as_usize()].push(edge);
+        Some(edge_id)
+    }
+}
+
+impl<Node, Weight: Clone, const DI: bool> Default for StableAdjacencyList<Node, Weight, DI> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Node, Weight, const DI: bool> StableAdjacencyList<Node, Weight, DI> {
+    fn node_id(&self, index: usize) -> StableNodeId {
+        let generation = self.generations[index];
+        StableNodeId::new_unchecked(StableId { index, generation })
+    }
+
+    /// Returns false if the node was removed or never existed.
+    pub fn is_valid(&self, node_id: StableNodeId) -> bool {
+        let StableId { index, generation } = node_id.raw();
+        matches!(self.nodes.get(index), Some(Some(_))) && self.generations[index] == generation
+    }
+
+    /// Frees the slot of the node, `None` if it was already removed.
+    pub fn try_remove_node(&mut self, node_id: StableNodeId) -> Option<Node> {
+        if !self.is_valid(node_id) {
+            return None;
+        }
+
+        let index = node_id.as_usize();
+
+        self.edges[index].clear();
+        for adj in self.edges.iter_mut() {
+            adj.retain(|edge| edge.to() != node_id);
+        }
+
+        self.generations[index] += 1;
+        self.free.push(index);
+        self.nodes[index].take()
+    }
+
+    fn adj(&self, node_id: StableNodeId) -> &[Edge<StableId, Weight>] {
+        if self.is_valid(node_id) {
+            &self.edges[node_id.as_usize()]
+        } else {
+            &[]
+        }
+    }
+
+    fn adj_mut(&mut self, node_id: StableNodeId) -> &mut [Edge<StableId, Weight>] {
+        if self.is_valid(node_id) {
+            &mut self.edges[node_id.as_usize()]
+        } else {
+            &mut []
+        }
+    }
+}
+
+/// Parallel edges fail with [GraphError::EdgeAlreadyExists].
+impl<Node, Weight: Copy, const DI: bool> TryFrom<EdgeList<Node, Weight, DI>>
+    for StableAdjacencyList<Node, Weight, DI>
+{
+    type Error = GraphError;
+
+    fn try_from(edge_list: EdgeList<Node, Weight, DI>) -> GraphResult<Self> {
+        edge_list.ensure_simple()?;
+
+        let EdgeList {
+            nodes,
+            edges,
+            node_count: _,
+        } = edge_list;
+
+        let mut adj_list = Self::with_nodes(nodes);
+
+        for (from, to, weight) in edges.into_iter() {
+            let from = adj_list.node_id(from);
+            let to = adj_list.node_id(to);
+            adj_list.insert_edge(from, to, weight);
+        }
+
+        Ok(adj_list)
+    }
+}
+
+impl<Node, Weight, const DI: bool> Base for StableAdjacencyList<Node, Weight, DI> {
+    type Id = StableId;
+    type Node = Node;
+    type Weight = Weight;
+}
+
+impl<Node, Weight, const DI: bool> Capacity for StableAdjacencyList<Node, Weight, DI> {
+    fn edges_capacity(&self) -> usize {
+        self.edges.iter().map(|adj| adj.capacity()).sum()
+    }
+
+    fn nodes_capacity(&self) -> usize {
+        self.nodes.capacity()
+    }
+}
+
+impl<Node, Weight, const DI: bool> Clear for StableAdjacencyList<Node, Weight, DI> {
+    /// Removes all nodes, their ids stay invalid after new nodes are inserted.
+    fn clear(&mut self) {
+        self.clear_edges();
+
+        for index in (0..self.nodes.len()).rev() {
+            if self.nodes[index].take().is_some() {
+                self.generations[index] += 1;
+                self.free.push(index);
+            }
+        }
+    }
+
+    fn clear_edges(&mut self) {
+        for adj in &mut self.edges {
+            adj.clear();
+        }
+    }
+}
+
+impl<Node: PartialEq, Weight, const DI: bool> Contains for StableAdjacencyList<Node, Weight, DI> {
+    fn contains_node(&self, node: &Node) -> Option<StableNodeId> {
+        self.nodes
+            .iter()
+            .position(|other| other.as_ref() == Some(node))
+            .map(|index| self.node_id(index))
+    }
+
+    fn contains_edge(&self, from: StableNodeId, to: StableNodeId) -> Option<StableEdgeId> {
+        self.adj(from).iter().find_map(|edge| {
+            if edge.to() == to {
+                Some(edge.edge_id)
+            } else {
+                None
+            }
+        })
+    }
+}
+
+impl<Node, Weight, const DI: bool> Count for StableAdjacencyList<Node, Weight, DI> {
+    fn edge_count(&self) -> usize {
+        let count = self.edges.iter().map(|adj| adj.len()).sum::<usize>();
+        let loops = self
+            .edges
+            .iter()
+            .flatten()
+            .filter(|edge| edge.from() == edge.to())
+            .count();
+
+        // undirected edges are stored twice, except for self loops
+        if DI {
+            count
+        } else {
+            (count + loops) / 2
+        }
+    }
+
+    fn node_count(&self) -> usize {
+        self.nodes.len() - self.free.len()
+    }
+
+    fn node_bound(&self) -> usize {
+        self.nodes.len()
+    }
+}
+
+impl<Node, Weight: Clone, const DI: bool> Create for StableAdjacencyList<Node, Weight, DI> {
+    fn with_capacity(nodes: usize, _edges: usize) -> Self {
+        Self {
+            nodes: Vec::with_capacity(nodes),
+            generations: Vec::with_capacity(nodes),
+            edges: Vec::with_capacity(nodes),
+            free: Vec::new(),
+        }
+    }
+
+    fn with_nodes(nodes: impl IntoIterator<Item = Node>) -> Self {
+        let nodes = nodes.into_iter().map(Some).collect::<Vec<_>>();
+        let generations = vec![0; nodes.len()];
+        let edges = vec![Vec::new(); nodes.len()];
+
+        Self {
+            nodes,
+            generations,
+            edges,
+            free: Vec::new(),
+        }
+    }
+}
+
+impl<Node, Weight, const DI: bool> Directed for StableAdjacencyList<Node, Weight, DI> {
+    fn directed() -> bool {
+        DI
+    }
+}
+
+impl<Node, Weight: Clone, const DI: bool> Extend for StableAdjacencyList<Node, Weight, DI> {
+    fn extend_edges(&mut self, edges: impl Iterator<Item = (StableNodeId, StableNodeId, Weight)>) {
+        for (from, to, weight) in edges.into_iter() {
+            self.insert_edge(from, to, weight);
+        }
+    }
+
+    fn extend_nodes(&mut self, nodes: impl Iterator<Item = Node>) {
+        for node in nodes {
+            self.insert_node(node);
+        }
+    }
+}
+
+impl<Node, Weight, const DI: bool> Get for StableAdjacencyList<Node, Weight, DI> {
+    fn node(&self, node_id: StableNodeId) -> Option<&Node> {
+        if self.is_valid(node_id) {
+            self.nodes[node_id.as_usize()].as_ref()
+        } else {
+            None
+        }
+    }
+
+    fn weight(&self, edge_id: StableEdgeId) -> Option<&Weight> {
+        self.adj(edge_id.from()).iter().find_map(|edge| {
+            if edge_id.matches(&edge.edge_id) {
+                Some(&edge.weight)
+            } else {
+                None
+            }
+        })
+    }
+}
+
+impl<Node, Weight, const DI: bool> GetMut for StableAdjacencyList<Node, Weight, DI> {
+    fn node_mut(&mut self, node_id: StableNodeId) -> Option<&mut Node> {
+        if self.is_valid(node_id) {
+            self.nodes[node_id.as_usize()].as_mut()
+        } else {
+            None
+        }
+    }
+
+    fn weight_mut(&mut self, edge_id: StableEdgeId) -> Option<&mut Weight> {
+        self.adj_mut(edge_id.from()).iter_mut().find_map(|edge| {
+            if edge_id.matches(&edge.edge_id) {
+                Some(&mut edge.weight)
+            } else {
+                None
+            }
+        })
+    }
+}
+
+impl<Node, Weight, const DI: bool> Index for StableAdjacencyList<Node, Weight, DI> {
+    type EdgeIds<'a> = impl Iterator<Item = StableEdgeId> + 'a
+    where Self: 'a;
+    type NodeIds<'a> = impl Iterator<Item = StableNodeId> + 'a
+    where Self: 'a;
+
+    fn edge_ids<'a>(&'a self) -> Self::EdgeIds<'a> {
+        self.edges
+            .iter()
+            .flat_map(|adj| adj.iter().map(|edge| edge.edge_id))
+    }
+
+    fn node_ids<'a>(&'a self) -> Self::NodeIds<'a> {
+        self.nodes
+            .iter()
+            .enumerate()
+            .filter(|(_, node)| node.is_some())
+            .map(|(index, _)| self.node_id(index))
+    }
+}
+
+impl<Node, Weight, const DI: bool> IndexAdjacent for StableAdjacencyList<Node, Weight, DI> {
+    type AdjacentEdgeIds<'a> = impl Iterator<Item = StableEdgeId> + 'a
+    where Self: 'a;
+    type AdjacentNodeIds<'a> = impl Iterator<Item = StableNodeId> + 'a
+    where Self: 'a;
+
+    fn adjacent_edge_ids<'a>(&'a self, node_id: StableNodeId) -> Self::AdjacentEdgeIds<'a> {
+        self.adj(node_id).iter().map(|edge| edge.edge_id)
+    }
+
+    fn adjacent_node_ids<'a>(&'a self, node_id: StableNodeId) -> Self::AdjacentNodeIds<'a> {
+        self.adj(node_id).iter().map(|edge| edge.to())
+    }
+}
+
+impl<Node, Weight, const DI: bool> Iter for StableAdjacencyList<Node, Weight, DI> {
+    type Nodes<'a> = impl Iterator<Item = &'a Node> + 'a
+    where
+        Node: 'a,
+        Self: 'a;
+
+    type Edges<'a> = impl Iterator<Item = EdgeRef<'a, StableId, Weight>> + 'a
+    where
+        Weight: 'a,
+        Self: 'a;
+
+    fn iter_nodes<'a>(&'a self) -> Self::Nodes<'a> {
+        self.nodes.iter().flatten()
+    }
+
+    fn iter_edges<'a>(&'a self) -> Self::Edges<'a> {
+        self.edges
+            .iter()
+            .flat_map(|adj| adj.iter())
+            .map(Into::into)
+    }
+}
+
+impl<Node, Weight, const DI: bool> IterMut for StableAdjacencyList<Node, Weight, DI> {
+    type NodesMut<'a> = impl Iterator<Item = &'a mut Node> + 'a
+    where
+        Node: 'a,
+        Self: 'a;
+
+    type EdgesMut<'a> = impl Iterator<Item = EdgeRefMut<'a, StableId, Weight>> + 'a
+    where
+        Weight: 'a,
+        Self: 'a;
+
+    fn iter_nodes_mut<'a>(&'a mut self) -> Self::NodesMut<'a> {
+        self.nodes.iter_mut().flatten()
+    }
+
+    fn iter_edges_mut<'a>(&'a mut self) -> Self::EdgesMut<'a> {
+        self.edges
+            .iter_mut()
+            .flat_map(|adj| adj.iter_mut())
+            .map(Into::into)
+    }
+}
+
+impl<Node, Weight, const DI: bool> IterAdjacent for StableAdjacencyList<Node, Weight, DI> {
+    type Nodes<'a> = impl Iterator<Item = &'a Node> + 'a
+    where
+        Node: 'a,
+        Self: 'a;
+
+    type Edges<'a> = impl Iterator<Item = EdgeRef<'a, StableId, Weight>> + 'a
+    where
+        Weight: 'a,
+        Self: 'a;
+
+    fn iter_adjacent_nodes<'a>(&'a self, node_id: StableNodeId) -> Self::Nodes<'a> {
+        self.adjacent_node_ids(node_id)
+            .map(|node_id| self.node(node_id).unwrap())
+    }
+
+    fn iter_adjacent_edges<'a>(&'a self, node_id: StableNodeId) -> Self::Edges<'a> {
+        self.adj(node_id).iter().map(Into::into)
+    }
+}
+
+impl<Node, Weight, const DI: bool> IterAdjacentMut for StableAdjacencyList<Node, Weight, DI> {
+    type NodesMut<'a> = impl Iterator<Item = &'a mut Node> + 'a
+    where
+        Node: 'a,
+        Self: 'a;
+
+    type EdgesMut<'a> = impl Iterator<Item = EdgeRefMut<'a, StableId, Weight>> + 'a
+    where
+        Weight: 'a,
+        Self: 'a;
+
+    fn iter_adjacent_nodes_mut<'a>(&'a mut self, node_id: StableNodeId) -> Self::NodesMut<'a> {
+        let ids = self
+            .adjacent_node_ids(node_id)
+            .map(|node_id| node_id.as_usize())
+            .collect::<Vec<_>>();
+        self.nodes
+            .iter_mut()
+            .enumerate()
+            .filter_map(move |(index, node)| {
+                if ids.contains(&index) {
+                    node.as_mut()
+                } else {
+                    None
+                }
+            })
+    }
+
+    fn iter_adjacent_edges_mut<'a>(&'a mut self, node_id: StableNodeId) -> Self::EdgesMut<'a> {
+        self.adj_mut(node_id).iter_mut().map(Into::into)
+    }
+}
+
+impl<Node, Weight: Clone, const DI: bool> Insert for StableAdjacencyList<Node, Weight, DI> {
+    /// Reuses the slot of a removed node if there is one.
+    fn insert_node(&mut self, node: Node) -> StableNodeId {
+        match self.free.pop() {
+            Some(index) => {
+                self.nodes[index] = Some(node);
+                self.node_id(index)
+            }
+            None => {
+                let index = self.nodes.len();
+                self.nodes.push(Some(node));
+                self.generations.push(0);
+                self.edges.push(Vec::new());
+                self.node_id(index)
+            }
+        }
+    }
+
+    /// Panics if one of the nodes was removed, see [StableAdjacencyList::try_insert_edge].
+    fn insert_edge(
+        &mut self,
+        from: StableNodeId,
+        to: StableNodeId,
+        weight: Weight,
+    ) -> StableEdgeId {
+        self.try_insert_edge(from, to, weight)
+            .expect("edge between removed nodes")
+    }
+}
+
+impl<Node, Weight, const DI: bool> Remove for StableAdjacencyList<Node, Weight, DI> {
+    /// Frees the slot of the node, the ids of all other nodes stay valid.
+    /// Panics if the node was already removed, see [StableAdjacencyList::try_remove_node].
+    fn remove_node(&mut self, node_id: StableNodeId) -> Node {
+        self.try_remove_node(node_id)
+            .expect("node was already removed")
+    }
+
+    fn remove_edge(&mut self, edge_id: StableEdgeId) -> Option<Weight> {
+        if !self.is_valid(edge_id.from()) {
+            return None;
+        }
+
+        let adj = &mut self.edges[edge_id.from().as_usize()];
+        let position = adj.iter().position(|edge| edge_id.matches(&edge.edge_id))?;
+        let edge = adj.remove(position);
+
+        if !DI && edge_id.from() != edge_id.to() {
+            let adj = &mut self.edges[edge_id.to().as_usize()];
+            if let Some(position) = adj.iter().position(|edge| edge.to() == edge_id.from()) {
+                adj.remove(position);
+            }
+        }
+
+        Some(edge.weight)
+    }
+}
+
+impl<Node, Weight, const DI: bool> Reserve for StableAdjacencyList<Node, Weight, DI> {
+    /// Reserves the additional edges spread evenly over all adjacency lists.
+    fn reserve_edges(&mut self, additional: usize) {
+        let count = self.edges.len();
+        if count == 0 {
+            return;
+        }
+
+        let per_node = additional.div_ceil(count);
+        for adj in self.edges.iter_mut() {
+            adj.reserve(per_node);
+        }
+    }
+
+    fn reserve_nodes(&mut self, additional: usize) {
+        let additional = additional.saturating_sub(self.free.len());
+        self.nodes.reserve(additional);
+        self.generations.reserve(additional);
+        self.edges.reserve(additional);
+    }
+}
+
+impl<Node: crate::graph::Node, Weight: crate::graph::Weight, const DI: bool> Graph<Node, Weight>
+    for StableAdjacencyList<Node, Weight, DI>
+{
+}
+
+impl<Node: crate::graph::Node, const DI: bool> WeightlessGraph<Node>
+    for StableAdjacencyList<Node, (), DI>
+{
+}
+
+#[cfg(test)]
+mod test {
+    extern crate test;
+    use super::StableAdjacencyList;
+    use crate::{
+        algorithms::{dijkstra, dijkstra_between},
+        graph::{test::*, Count, Create, Get, Index, Insert, Remove},
+        prelude::*,
+        test::digraph,
+    };
+
+    #[test]
+    pub fn stable_adj_list_create_with_nodes() {
+        graph_create_with_nodes::<StableAdjacencyList<_, _>>()
+    }
+
+    #[test]
+    pub fn stable_adj_list_insert_and_contains() {
+        graph_insert_and_contains::<StableAdjacencyList<_, _>>()
+    }
+
+    #[test]
+    pub fn stable_adj_list_clear() {
+        graph_clear::<StableAdjacencyList<_, _>>()
+    }
+
+    #[test]
+    pub fn stable_adj_list_get() {
+        graph_get::<StableAdjacencyList<_, _>>()
+    }
+
+    #[test]
+    pub fn stable_adj_list_index() {
+        graph_index::<StableAdjacencyList<_, _>>()
+    }
+
+    #[test]
+    pub fn stable_adj_list_index_adjacent() {
+        graph_index_adjacent::<StableAdjacencyList<_, _>>()
+    }
+
+    #[test]
+    pub fn stable_adj_list_remove_edge() {
+        graph_remove_edge::<StableAdjacencyList<_, _>>()
+    }
+
+    #[test]
+    pub fn stable_adj_list_remove_edge_directed() {
+        graph_remove_edge::<StableAdjacencyList<_, _, true>>()
+    }
+
+    #[test]
+    pub fn stable_adj_list_reserve() {
+        graph_reserve::<StableAdjacencyList<_, _>>()
+    }
+
+    #[test]
+    pub fn stable_adj_list_remove_node() {
+        let mut graph = StableAdjacencyList::<usize, f32, true>::with_nodes([1, 2, 3, 4]);
+        let ids = graph.node_ids().collect::<Vec<_>>();
+        graph.insert_edge(ids[0], ids[1], 2.0);
+        graph.insert_edge(ids[1], ids[2], 3.0);
+        let three_four = graph.insert_edge(ids[2], ids[3], 4.0);
+
+        assert_eq!(graph.remove_node(ids[1]), 2);
+        assert_eq!(graph.node_count(), 3);
+        assert_eq!(graph.node_bound(), 4);
+        assert_eq!(graph.edge_count(), 1);
+        assert!(!graph.node_ids().any(|node_id| node_id == ids[1]));
+
+        // the other ids stay valid
+        assert_eq!(graph.node(ids[2]), Some(&3));
+        assert_eq!(graph.weight(three_four), Some(&4.0));
+
+        // the slot is reused, the old id is detected as stale
+        let five = graph.insert_node(5);
+        assert_eq!(five.as_usize(), ids[1].as_usize());
+        assert_ne!(five, ids[1]);
+        assert!(!graph.is_valid(ids[1]));
+        assert_eq!(graph.node(ids[1]), None);
+        assert_eq!(graph.node(five), Some(&5));
+        assert_eq!(graph.node_bound(), 4);
+
+        // stale ids are rejected instead of touching the reused slot
+        assert_eq!(graph.try_insert_edge(ids[1], ids[2], 1.0), None);
+        assert_eq!(graph.try_remove_node(ids[1]), None);
+        assert_eq!(graph.edge_count(), 1);
+        assert_eq!(graph.try_remove_node(five), Some(5));
+    }
+
+    #[test]
+    pub fn stable_adj_list_dijkstra_after_remove() {
+        let mut graph: StableAdjacencyList<usize, f64, true> = digraph("data/Wege1.txt").unwrap();
+        let mut reference: AdjacencyList<usize, f64, true> = digraph("data/Wege1.txt").unwrap();
+
+        // leaves a gap in the ids of the stable graph
+        let four = graph.node_ids().nth(4).unwrap();
+        graph.remove_node(four);
+        let four = reference.node_ids().nth(4).unwrap();
+        reference.remove_node(four);
+
+        for (from, reference_from) in graph.node_ids().zip(reference.node_ids()) {
+            for (to, reference_to) in graph.node_ids().zip(reference.node_ids()) {
+                assert_eq!(
+                    dijkstra_between(&graph, from, to),
+                    dijkstra_between(&reference, reference_from, reference_to)
+                );
+            }
+        }
+    }
+
+    #[test]
+    pub fn stable_adj_list_parents_after_reuse() {
+        let mut graph = StableAdjacencyList::<usize, f64, true>::default();
+        let a = graph.insert_node(0);
+        let b = graph.insert_node(1);
+        graph.remove_node(b);
+        let c = graph.insert_node(2);
+        graph.insert_edge(a, c, 1.0);
+
+        let distances = dijkstra(&graph, a, c).unwrap();
+        let edge_ids = distances.parents.edge_ids().collect::<Vec<_>>();
+
+        assert_eq!(edge_ids.len(), 1);
+        assert_eq!(edge_ids[0].to(), c);
+        assert_eq!(graph.weight(edge_ids[0]), Some(&1.0));
+
+        graph.remove_node(c);
+        assert_eq!(distances.parents.edge_ids_in(&graph).count(), 0);
+    }
+}
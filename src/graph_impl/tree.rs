@@ -73,7 +73,7 @@ pub struct Tree<'a, G: Base> {
 
 impl<'a, G: Base + Count> Tree<'a, G> {
     pub fn new(root: NodeId<G::Id>, graph: &'a G) -> Self {
-        let count = graph.node_count();
+        let count = graph.node_bound();
 
         Self {
             root,
@@ -0,0 +1,526 @@
+use crate::{
+    edge_list::{EdgeDirections, EdgeList},
+    error::{GraphError, GraphResult},
+    graph::{BalancedNode, Count, Directed, FlowWeight, Index, Iter},
+    structures::SparseMatrixBuilder,
+};
+use roxmltree::{Document, Node};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
+
+/// A typed value of a GraphML attribute
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Boolean(bool),
+    Int(i64),
+    Double(f64),
+    String(String),
+}
+
+impl Value {
+    /// Returns the value of numeric attributes
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Self::Int(value) => Some(*value as f64),
+            Self::Double(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Parses the text of a `<data>` or `<default>` element as `attr.type`
+    fn parse(ty: &str, name: &str, text: &str) -> GraphResult<Self> {
+        let text = text.trim();
+        let invalid = || GraphError::InvalidAttribute {
+            name: name.to_string(),
+            value: text.to_string(),
+        };
+
+        Ok(match ty {
+            "boolean" => Self::Boolean(text.parse().map_err(|_| invalid())?),
+            "int" | "long" => Self::Int(text.parse().map_err(|_| invalid())?),
+            "float" | "double" => Self::Double(text.parse().map_err(|_| invalid())?),
+            _ => Self::String(text.to_string()),
+        })
+    }
+
+    fn ty(&self) -> &'static str {
+        match self {
+            Self::Boolean(_) => "boolean",
+            Self::Int(_) => "long",
+            Self::Double(_) => "double",
+            Self::String(_) => "string",
+        }
+    }
+}
+
+impl Display for Value {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Boolean(value) => write!(f, "{value}"),
+            Self::Int(value) => write!(f, "{value}"),
+            Self::Double(value) => write!(f, "{value}"),
+            Self::String(value) => write!(f, "{}", escape(value)),
+        }
+    }
+}
+
+/// The attributes of a node or an edge by their `attr.name`
+pub type Attributes = BTreeMap<String, Value>;
+
+/// Returns a numeric attribute
+pub fn number(attributes: &Attributes, name: &str) -> GraphResult<f64> {
+    match attributes.get(name) {
+        Some(value) => value.as_f64().ok_or_else(|| GraphError::InvalidAttribute {
+            name: name.to_string(),
+            value: value.to_string(),
+        }),
+        None => Err(GraphError::MissingAttribute {
+            element: "data".to_string(),
+            name: name.to_string(),
+        }),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphMlNode {
+    pub id: String,
+    pub attributes: Attributes,
+}
+
+/// A single graph of a GraphML document.
+/// The nodes are numbered in document order and the `<key>` declarations
+/// are resolved, so the attributes are stored by their name.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphMl {
+    pub directed: bool,
+    pub nodes: Vec<GraphMlNode>,
+    pub edges: Vec<(usize, usize, Attributes)>,
+}
+
+/// Escapes the characters which are not allowed in XML text and attribute values
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn attribute<'a>(element: &Node<'a, '_>, name: &str) -> GraphResult<&'a str> {
+    element
+        .attribute(name)
+        .ok_or_else(|| GraphError::MissingAttribute {
+            element: element.tag_name().name().to_string(),
+            name: name.to_string(),
+        })
+}
+
+struct Key {
+    domain: String,
+    name: String,
+    ty: String,
+    default: Option<Value>,
+}
+
+impl Key {
+    fn applies(&self, domain: &str) -> bool {
+        self.domain == domain || self.domain == "all"
+    }
+}
+
+/// Collects the `<data>` children of an element, missing attributes get their default
+fn attributes(element: &Node, keys: &HashMap<&str, Key>, domain: &str) -> GraphResult<Attributes> {
+    let mut attributes = keys
+        .values()
+        .filter(|key| key.applies(domain))
+        .filter_map(|key| Some((key.name.clone(), key.default.clone()?)))
+        .collect::<Attributes>();
+
+    for data in element
+        .children()
+        .filter(|child| child.has_tag_name("data"))
+    {
+        let id = attribute(&data, "key")?;
+        let key = keys.get(id).ok_or_else(|| GraphError::InvalidAttribute {
+            name: "key".to_string(),
+            value: id.to_string(),
+        })?;
+
+        let value = Value::parse(&key.ty, &key.name, data.text().unwrap_or_default())?;
+        attributes.insert(key.name.clone(), value);
+    }
+
+    Ok(attributes)
+}
+
+impl FromStr for GraphMl {
+    type Err = GraphError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let document = Document::parse(s)?;
+        let root = document.root_element();
+
+        let mut keys = HashMap::new();
+        for key in root.children().filter(|child| child.has_tag_name("key")) {
+            let id = attribute(&key, "id")?;
+            let name = key.attribute("attr.name").unwrap_or(id).to_string();
+            let ty = key.attribute("attr.type").unwrap_or("string").to_string();
+            let default = key
+                .children()
+                .find(|child| child.has_tag_name("default"))
+                .map(|default| Value::parse(&ty, &name, default.text().unwrap_or_default()))
+                .transpose()?;
+
+            let domain = key.attribute("for").unwrap_or("all").to_string();
+            keys.insert(
+                id,
+                Key {
+                    domain,
+                    name,
+                    ty,
+                    default,
+                },
+            );
+        }
+
+        let graph = root
+            .children()
+            .find(|child| child.has_tag_name("graph"))
+            .ok_or_else(|| GraphError::MissingAttribute {
+                element: "graphml".to_string(),
+                name: "graph".to_string(),
+            })?;
+        let directed = attribute(&graph, "edgedefault")? == "directed";
+
+        let mut index = HashMap::new();
+        let mut nodes = Vec::new();
+        for node in graph.children().filter(|child| child.has_tag_name("node")) {
+            let id = attribute(&node, "id")?;
+            index.insert(id, nodes.len());
+            nodes.push(GraphMlNode {
+                id: id.to_string(),
+                attributes: attributes(&node, &keys, "node")?,
+            });
+        }
+
+        let node_index = |id: &str| {
+            index
+                .get(id)
+                .copied()
+                .ok_or_else(|| GraphError::UnknownNode(id.to_string()))
+        };
+
+        let mut edges = Vec::new();
+        for edge in graph.children().filter(|child| child.has_tag_name("edge")) {
+            let source = node_index(attribute(&edge, "source")?)?;
+            let target = node_index(attribute(&edge, "target")?)?;
+            edges.push((source, target, attributes(&edge, &keys, "edge")?));
+        }
+
+        Ok(Self {
+            directed,
+            nodes,
+            edges,
+        })
+    }
+}
+
+impl GraphMl {
+    /// Copies any graph, `node` and `edge` turn the payloads into attributes.
+    /// The nodes get the ids `n0, n1, ...` and undirected edges are kept once.
+    pub fn from_graph<G>(
+        graph: &G,
+        node: impl Fn(&G::Node) -> Attributes,
+        edge: impl Fn(&G::Weight) -> Attributes,
+    ) -> Self
+    where
+        G: Index + Iter + Count + Directed,
+    {
+        let mut index = vec![None; graph.node_bound()];
+        for (i, node_id) in graph.node_ids().enumerate() {
+            index[node_id.as_usize()] = Some(i);
+        }
+
+        let nodes = graph
+            .iter_nodes()
+            .enumerate()
+            .map(|(i, payload)| GraphMlNode {
+                id: format!("n{i}"),
+                attributes: node(payload),
+            })
+            .collect();
+
+        let directions = EdgeDirections::new(graph, G::directed());
+        let edges = graph
+            .iter_edges()
+            .filter(|e| directions.keep(e.from().as_usize(), e.to().as_usize()))
+            .map(|e| {
+                let from = index[e.from().as_usize()].unwrap();
+                let to = index[e.to().as_usize()].unwrap();
+                (from, to, edge(e.weight))
+            })
+            .collect();
+
+        Self {
+            directed: G::directed(),
+            nodes,
+            edges,
+        }
+    }
+
+    /// Builds an edge list, `node` and `edge` create the payloads from the attributes.
+    /// Fails if the `edgedefault` of the graph does not match `DI`, parallel edges are kept.
+    pub fn edge_list<N, W, const DI: bool>(
+        &self,
+        node: impl Fn(usize, &Attributes) -> GraphResult<N>,
+        edge: impl Fn(&Attributes) -> GraphResult<W>,
+    ) -> GraphResult<EdgeList<N, W, DI>> {
+        if self.directed != DI {
+            return Err(GraphError::EdgeDefault {
+                directed: self.directed,
+            });
+        }
+
+        let nodes = self
+            .nodes
+            .iter()
+            .enumerate()
+            .map(|(i, n)| node(i, &n.attributes))
+            .collect::<GraphResult<Vec<_>>>()?;
+
+        let mut edges = SparseMatrixBuilder::with_capacity(nodes.len(), nodes.len());
+        for (from, to, attributes) in &self.edges {
+            edges.insert(*from, *to, edge(attributes)?);
+        }
+
+        Ok(EdgeList {
+            node_count: nodes.len(),
+            edges: edges.build_all(),
+            nodes,
+        })
+    }
+
+    /// Uses the numeric attribute `cost` as the weight of the edges
+    pub fn weighted<const DI: bool>(&self, cost: &str) -> GraphResult<EdgeList<usize, f64, DI>> {
+        self.edge_list(|i, _| Ok(i), |attributes| number(attributes, cost))
+    }
+
+    /// Picks the attributes for the balance of the nodes and the capacity and cost of the edges
+    pub fn balanced<const DI: bool>(
+        &self,
+        balance: &str,
+        capacity: &str,
+        cost: &str,
+    ) -> GraphResult<EdgeList<BalancedNode<usize, f64>, FlowWeight<f64>, DI>> {
+        self.edge_list(
+            |i, attributes| Ok(BalancedNode::new(i, number(attributes, balance)?)),
+            |attributes| {
+                let capacity = number(attributes, capacity)?;
+                let cost = number(attributes, cost)?;
+                Ok(FlowWeight::new(capacity, cost, 0.0))
+            },
+        )
+    }
+}
+
+impl Display for GraphMl {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        // the keys are declared by the first value of every attribute
+        let mut keys = BTreeMap::new();
+        let node_attributes = self.nodes.iter().map(|node| ("node", &node.attributes));
+        let edge_attributes = self.edges.iter().map(|(_, _, a)| ("edge", a));
+
+        for (domain, attributes) in node_attributes.chain(edge_attributes) {
+            for (name, value) in attributes {
+                keys.entry((domain, name.as_str())).or_insert(value.ty());
+            }
+        }
+
+        let ids = keys
+            .keys()
+            .enumerate()
+            .map(|(i, key)| (*key, format!("d{i}")))
+            .collect::<HashMap<_, _>>();
+
+        writeln!(f, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+        writeln!(
+            f,
+            r#"<graphml xmlns="http://graphml.graphdrawing.org/xmlns">"#
+        )?;
+
+        for ((domain, name), ty) in &keys {
+            let id = &ids[&(*domain, *name)];
+            let name = escape(name);
+            writeln!(
+                f,
+                r#"  <key id="{id}" for="{domain}" attr.name="{name}" attr.type="{ty}"/>"#
+            )?;
+        }
+
+        let edgedefault = if self.directed {
+            "directed"
+        } else {
+            "undirected"
+        };
+        writeln!(f, r#"  <graph id="G" edgedefault="{edgedefault}">"#)?;
+
+        let write_data = |f: &mut Formatter<'_>, domain, attributes: &Attributes| {
+            for (name, value) in attributes {
+                let id = &ids[&(domain, name.as_str())];
+                writeln!(f, r#"      <data key="{id}">{value}</data>"#)?;
+            }
+            Ok(())
+        };
+
+        for node in &self.nodes {
+            writeln!(f, r#"    <node id="{}">"#, escape(&node.id))?;
+            write_data(f, "node", &node.attributes)?;
+            writeln!(f, "    </node>")?;
+        }
+
+        for (from, to, attributes) in &self.edges {
+            let source = escape(&self.nodes[*from].id);
+            let target = escape(&self.nodes[*to].id);
+            writeln!(f, r#"    <edge source="{source}" target="{target}">"#)?;
+            write_data(f, "edge", attributes)?;
+            writeln!(f, "    </edge>")?;
+        }
+
+        writeln!(f, "  </graph>")?;
+        writeln!(f, "</graphml>")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Attributes, GraphMl, Value};
+    use crate::{
+        algorithms::cycle_canceling,
+        edge_list::EdgeList,
+        error::GraphError,
+        graph::{Count, Get, NodeBalance},
+        prelude::*,
+        test::{bgraph, id},
+    };
+    use std::str::FromStr;
+
+    const UNDIRECTED: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<graphml xmlns="http://graphml.graphdrawing.org/xmlns">
+  <key id="w" for="edge" attr.name="weight" attr.type="int">
+    <default>1</default>
+  </key>
+  <key id="c" for="node" attr.name="color" attr.type="string"/>
+  <graph id="G" edgedefault="undirected">
+    <node id="a"><data key="c">red &amp; blue</data></node>
+    <node id="b"/>
+    <node id="c"/>
+    <edge source="a" target="b"><data key="w">3</data></edge>
+    <edge source="c" target="b"/>
+  </graph>
+</graphml>
+"#;
+
+    #[test]
+    fn graphml_undirected() {
+        let graphml = GraphMl::from_str(UNDIRECTED).unwrap();
+
+        assert!(!graphml.directed);
+        assert_eq!(
+            graphml.nodes[0].attributes.get("color"),
+            Some(&Value::String("red & blue".to_string()))
+        );
+
+        let edge_list = graphml.weighted::<false>("weight").unwrap();
+        let graph = AdjacencyList::<usize, f64>::try_from(edge_list).unwrap();
+
+        assert_eq!(
+            graph.weight(EdgeId::new_unchecked(id(1), id(0))),
+            Some(&3.0)
+        );
+        assert_eq!(
+            graph.weight(EdgeId::new_unchecked(id(1), id(2))),
+            Some(&1.0)
+        );
+
+        assert!(matches!(
+            graphml.weighted::<true>("weight"),
+            Err(GraphError::EdgeDefault { directed: false })
+        ));
+
+        let written = graphml.to_string();
+        assert_eq!(GraphMl::from_str(&written).unwrap(), graphml);
+    }
+
+    #[test]
+    fn graphml_balanced_round_trip() {
+        let graph: AdjacencyList<_, _, true> = bgraph("data/Kostenminimal1.txt").unwrap();
+
+        let graphml = GraphMl::from_graph(
+            &graph,
+            |node| Attributes::from([("balance".to_string(), Value::Double(*node.balance()))]),
+            |weight| {
+                Attributes::from([
+                    ("capacity".to_string(), Value::Double(weight.capacity)),
+                    ("cost".to_string(), Value::Double(weight.cost)),
+                ])
+            },
+        );
+
+        let parsed = GraphMl::from_str(&graphml.to_string()).unwrap();
+        let edge_list = parsed
+            .balanced::<true>("balance", "capacity", "cost")
+            .unwrap();
+        assert_eq!(edge_list, EdgeList::from(&graph));
+
+        let graph = AdjacencyList::<_, _, true>::try_from(edge_list).unwrap();
+        assert_eq!(cycle_canceling(&graph), 3.0);
+    }
+
+    #[test]
+    fn graphml_errors() {
+        let unknown = UNDIRECTED.replace(r#"source="c""#, r#"source="d""#);
+        assert!(matches!(
+            GraphMl::from_str(&unknown),
+            Err(GraphError::UnknownNode(node)) if node == "d"
+        ));
+
+        let graphml = GraphMl::from_str(UNDIRECTED).unwrap();
+        assert!(matches!(
+            graphml.weighted::<false>("cost"),
+            Err(GraphError::MissingAttribute { .. })
+        ));
+        assert!(matches!(
+            GraphMl::from_str("<graphml><graph/></graphml>"),
+            Err(GraphError::MissingAttribute { .. })
+        ));
+    }
+
+    #[test]
+    fn graphml_parallel_edges() {
+        let edge = r#"<edge source="c" target="b"/>"#;
+        let parallel = UNDIRECTED.replace(edge, &format!("{edge}{edge}"));
+
+        let graphml = GraphMl::from_str(&parallel).unwrap();
+        assert_eq!(graphml.edges.len(), 3);
+
+        let edge_list = graphml.weighted::<false>("weight").unwrap();
+        assert_eq!(edge_list.edges.nnz(), 3);
+
+        let multi = AdjacencyList::<usize, f64, false, true>::try_from(edge_list.clone()).unwrap();
+        assert_eq!(multi.edge_count(), 3);
+        assert!(matches!(
+            AdjacencyList::<usize, f64>::try_from(edge_list),
+            Err(GraphError::EdgeAlreadyExists {
+                from: 1,
+                to: 2,
+                line: None
+            })
+        ));
+
+        // the parallel edges are written once each
+        let written = GraphMl::from_graph(&multi, |_| Attributes::new(), |_| Attributes::new());
+        assert_eq!(written.edges.len(), 3);
+    }
+}
@@ -8,11 +8,18 @@
 #![feature(associated_type_bounds)]
 
 pub mod algorithms;
+pub mod dimacs;
+pub mod dot;
 pub mod edge_list;
 pub mod error;
 pub mod graph;
 pub mod graph_impl;
+pub mod graphml;
+pub mod matrix_market;
+pub mod snapshot;
+pub mod stream;
 pub mod structures;
+pub mod tsplib;
 
 pub mod prelude {
     pub use crate::edge_list::EdgeList;
@@ -26,7 +33,7 @@ pub mod prelude {
 #[cfg(test)]
 pub mod test {
     use crate::{
-        error::GraphResult,
+        error::{GraphError, GraphResult},
         graph::{BalancedNode, Base, FlowWeight},
         prelude::{EdgeList, NodeId},
     };
@@ -48,44 +55,40 @@ pub mod test {
     pub fn weightless_undigraph<G, P>(path: P) -> GraphResult<G>
     where
         P: AsRef<Path>,
-        G: From<EdgeList<usize, (), false>>,
+        G: TryFrom<EdgeList<usize, (), false>, Error = GraphError>,
     {
         let content = fs::read_to_string(path)?;
         let edge_list = EdgeList::from_str(&content)?;
-        let graph = G::from(edge_list);
-        Ok(graph)
+        G::try_from(edge_list)
     }
 
     pub fn undigraph<G, P>(path: P) -> GraphResult<G>
     where
         P: AsRef<Path>,
-        G: From<EdgeList<usize, f64, false>>,
+        G: TryFrom<EdgeList<usize, f64, false>, Error = GraphError>,
     {
         let content = fs::read_to_string(path)?;
         let edge_list = EdgeList::from_str(&content)?;
-        let graph = G::from(edge_list);
-        Ok(graph)
+        G::try_from(edge_list)
     }
 
     pub fn digraph<G, P>(path: P) -> GraphResult<G>
     where
         P: AsRef<Path>,
-        G: From<EdgeList<usize, f64, true>>,
+        G: TryFrom<EdgeList<usize, f64, true>, Error = GraphError>,
     {
         let content = fs::read_to_string(path)?;
         let edge_list = EdgeList::from_str(&content)?;
-        let graph = G::from(edge_list);
-        Ok(graph)
+        G::try_from(edge_list)
     }
 
     pub fn bgraph<G, P>(path: P) -> GraphResult<G>
     where
         P: AsRef<Path>,
-        G: From<EdgeList<BalancedNode<usize, f64>, FlowWeight<f64>, true>>,
+        G: TryFrom<EdgeList<BalancedNode<usize, f64>, FlowWeight<f64>, true>, Error = GraphError>,
     {
         let content = fs::read_to_string(path)?;
         let edge_list = EdgeList::from_str(&content)?;
-        let graph = G::from(edge_list);
-        Ok(graph)
+        G::try_from(edge_list)
     }
 }
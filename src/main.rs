@@ -1,226 +1,441 @@
-use grph::prelude::*;
-use pathfinding::prelude::{edmonds_karp, DenseCapacity, SparseCapacity};
-use petgraph::{algo::find_negative_cycle, prelude::DiGraph};
-use std::{fs, str::FromStr, time::Instant};
-
-fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let (_, cost, _) = edmonds_karp::<_, i32, _, DenseCapacity<_>>(
-        &[0, 1, 2, 3, 4, 5, 6],
-        &5,
-        &6,
-        [
-            ((0, 2), 2),
-            ((0, 4), 5),
-            ((2, 4), 3),
-            ((3, 1), 2),
-            ((3, 4), 2),
-            // source
-            ((5, 0), 4),
-            ((5, 3), 2),
-            // sink
-            ((1, 6), 1),
-            ((4, 6), 5),
-            // reverse
-            ((2, 0), 0),
-            ((4, 0), 0),
-            ((4, 2), 0),
-            ((1, 3), 0),
-            ((4, 3), 0),
-            // source
-            ((0, 5), 0),
-            ((3, 5), 0),
-            // sink
-            ((6, 1), 0),
-            ((6, 4), 0),
-        ],
-    );
-
-    dbg!(cost);
-
-    let residual_graph = DiGraph::<i32, f32>::from_edges([
-        (4, 2, 3.0),
-        (0, 5, 0.0),
-        (5, 3, 0.0),
-        (2, 0, -2.0),
-        (2, 4, -3.0),
-        (4, 3, -1.0),
-        (0, 2, 2.0),
-        (1, 3, -2.0),
-        (3, 1, 2.0),
-        (3, 5, 0.0),
-        (4, 0, -1.0),
-        (5, 0, 0.0),
-        (3, 4, 1.0),
-        (0, 4, 1.0),
-        (1, 6, 0.0),
-        (6, 1, 0.0),
-        (4, 6, 0.0),
-        (6, 4, 0.0),
-    ]);
-    dbg!(find_negative_cycle(&residual_graph, 0.into())); // ist auch 4, 2, 0
-
-    // nearest_neighbor("data/K_10.txt");
-    // nearest_neighbor("data/K_10e.txt");
-    // nearest_neighbor("data/K_12.txt");
-    // nearest_neighbor("data/K_12e.txt");
-    // nearest_neighbor("data/K_15.txt");
-    // nearest_neighbor("data/K_15e.txt");
-    // nearest_neighbor("data/K_20.txt");
-    // nearest_neighbor("data/K_30.txt");
-    // nearest_neighbor("data/K_50.txt");
-    // nearest_neighbor("data/K_70.txt");
-    // nearest_neighbor("data/K_100.txt");
-
-    // double_tree("data/K_10.txt");
-    // double_tree("data/K_10e.txt");
-    // double_tree("data/K_12.txt");
-    // double_tree("data/K_12e.txt");
-    // double_tree("data/K_15.txt");
-    // double_tree("data/K_15e.txt");
-    // double_tree("data/K_20.txt");
-    // double_tree("data/K_30.txt");
-    // double_tree("data/K_50.txt");
-    // double_tree("data/K_70.txt");
-    // double_tree("data/K_100.txt");
-
-    // branch_bound("data/K_10.txt");
-
-    // brute_force("data/K_10.txt");
-
-    // db();
-
-    // depth_search("data/K_10.txt");
-
-    Ok(())
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use grph::{
+    algorithms::{
+        bellman_ford_between, bfs_scc, branch_bound, brute_force, capacity_scaling,
+        cycle_canceling, dijkstra_between, double_tree, kruskal, nearest_neighbor_from_first, prim,
+        Mcf,
+    },
+    dimacs::{Dimacs, Problem},
+    graph::{BalancedNode, Count, Create, FlowWeight, Index, Insert, Iter},
+    graphml::GraphMl,
+    matrix_market::MatrixReader,
+    prelude::*,
+    tsplib::Tsplib,
+};
+use serde_json::json;
+use std::{
+    error::Error,
+    fs,
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+type WeightedGraph<const DI: bool> = AdjacencyList<usize, f64, DI>;
+type FlowGraph = MultiAdjacencyList<BalancedNode<usize, f64>, FlowWeight<f64>, true>;
+
+/// Runs graph algorithms on graph files
+#[derive(Parser)]
+#[command(name = "grph", version)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+    /// Print the result as JSON
+    #[arg(long, global = true)]
+    json: bool,
 }
 
-pub fn brute_force(path: &str) {
-    let edge_list = fs::read_to_string(path).unwrap();
-    let edge_list = EdgeList::from_str(&edge_list).unwrap();
-    let graph = AdjacencyList::<usize, f64>::try_from(edge_list).unwrap();
-
-    let now = Instant::now();
-
-    let total = graph.brute_force().unwrap().1 as f32;
-
-    println!("bf: {path}: {total} in {:?}", now.elapsed());
+#[derive(Subcommand)]
+enum Command {
+    /// Counts the connected components
+    Components {
+        #[command(flatten)]
+        input: Input,
+    },
+    /// Computes the weight of a minimum spanning tree
+    Mst {
+        #[command(flatten)]
+        input: Input,
+        #[arg(long, value_enum, default_value_t = Mst::Kruskal)]
+        algo: Mst,
+    },
+    /// Computes the distance between two nodes
+    Sp {
+        #[command(flatten)]
+        input: Input,
+        #[arg(long)]
+        from: usize,
+        #[arg(long)]
+        to: usize,
+        #[arg(long, value_enum, default_value_t = Sp::Dijkstra)]
+        algo: Sp,
+    },
+    /// Computes a tour through all nodes
+    Tsp {
+        #[command(flatten)]
+        input: Input,
+        #[arg(long, value_enum, default_value_t = Tsp::Nn)]
+        algo: Tsp,
+    },
+    /// Computes a maximal flow, the weights are used as capacities
+    Maxflow {
+        #[command(flatten)]
+        input: Input,
+        /// Defaults to the source of a DIMACS file
+        #[arg(long)]
+        source: Option<usize>,
+        /// Defaults to the sink of a DIMACS file
+        #[arg(long)]
+        sink: Option<usize>,
+    },
+    /// Computes the cost of a minimal cost flow by cycle canceling
+    Mcf {
+        #[command(flatten)]
+        input: Input,
+    },
 }
 
-pub fn branch_bound(path: &str) {
-    let edge_list = fs::read_to_string(path).unwrap();
-    let edge_list = EdgeList::from_str(&edge_list).unwrap();
-    let graph = AdjacencyList::<usize, f64>::try_from(edge_list).unwrap();
-
-    let now = Instant::now();
-
-    let total = graph.branch_bound().unwrap().1 as f32;
-
-    println!("bb: {path}: {total} in {:?}", now.elapsed());
+#[derive(Args)]
+struct Input {
+    path: PathBuf,
+    /// Guessed from the file extension if missing
+    #[arg(long, value_enum)]
+    format: Option<Format>,
+    #[arg(long)]
+    directed: bool,
+    /// The edges of edge list files carry a weight
+    #[arg(long)]
+    weighted: bool,
+    /// The nodes carry balances and the edges cost and capacity
+    #[arg(long)]
+    balanced: bool,
+    /// The GraphML attribute of the edge weight or cost
+    #[arg(long, default_value = "weight")]
+    cost: String,
+    /// The GraphML attribute of the edge capacity
+    #[arg(long, default_value = "capacity")]
+    capacity: String,
+    /// The GraphML attribute of the node balance
+    #[arg(long, default_value = "balance")]
+    balance: String,
+    /// The matrix entry standing for a missing edge, like 0 or inf
+    #[arg(long)]
+    no_edge: Option<f64>,
 }
 
-pub fn nearest_neighbor(path: &str) {
-    let edge_list = fs::read_to_string(path).unwrap();
-    let edge_list = EdgeList::from_str(&edge_list).unwrap();
-    let graph = AdjacencyList::<usize, f64>::try_from(edge_list).unwrap();
-    let total = graph.nearest_neighbor_from_first().unwrap().1 as f32;
-
-    let now = Instant::now();
-
-    println!("nn: {path}: {total} in {:?}", now.elapsed());
+#[derive(Clone, Copy, ValueEnum)]
+enum Format {
+    EdgeList,
+    Dimacs,
+    Tsplib,
+    Graphml,
+    MatrixMarket,
+    /// One row of a distance matrix per line
+    Matrix,
 }
 
-pub fn double_tree(path: &str) {
-    let edge_list = fs::read_to_string(path).unwrap();
-    let edge_list = EdgeList::from_str(&edge_list).unwrap();
-    let graph = AdjacencyList::<usize, f64>::try_from(edge_list).unwrap();
-    let total = graph.double_tree().unwrap().1 as f32;
-
-    let now = Instant::now();
+#[derive(Clone, Copy, ValueEnum)]
+enum Mst {
+    Kruskal,
+    Prim,
+}
 
-    println!("dt: {path}: {total} in {:?}", now.elapsed());
+#[derive(Clone, Copy, ValueEnum)]
+enum Sp {
+    Dijkstra,
+    BellmanFord,
 }
 
-pub fn db() {
-    let edge_list = EdgeList::with(
-        [
-            (0, 1, 1.0),
-            (1, 2, 1.0),
-            (2, 3, 1.0),
-            (3, 0, 10.0),
-            (0, 2, 2.0),
-            (1, 3, 2.0),
-        ]
-        .into_iter(),
-        4,
-    );
-    let graph = AdjacencyList::<usize, f64>::try_from(edge_list).unwrap();
-    let total = graph.double_tree().unwrap().1;
-
-    println!("{total}")
+#[derive(Clone, Copy, ValueEnum)]
+enum Tsp {
+    /// Nearest neighbor
+    Nn,
+    /// Double tree
+    Dt,
+    /// Branch and bound
+    Bb,
+    /// Brute force
+    Bf,
 }
 
-pub fn nn() {
-    let edge_list = EdgeList::with(
-        [
-            (0, 1, 1.0),
-            (1, 2, 1.0),
-            (2, 3, 1.0),
-            (3, 0, 10.0),
-            (0, 2, 2.0),
-            (1, 3, 2.0),
-        ]
-        .into_iter(),
-        4,
-    );
-    let graph = AdjacencyList::<usize, f64>::try_from(edge_list).unwrap();
-    let nn = graph.nearest_neighbor_from_first().unwrap().1;
-
-    println!("{nn}")
+impl Input {
+    fn format(&self) -> Format {
+        if let Some(format) = self.format {
+            return format;
+        }
+
+        match self.path.extension().and_then(|ext| ext.to_str()) {
+            Some("min" | "max" | "dimacs") => Format::Dimacs,
+            Some("tsp") => Format::Tsplib,
+            Some("graphml" | "xml") => Format::Graphml,
+            Some("mtx") => Format::MatrixMarket,
+            _ => Format::EdgeList,
+        }
+    }
+
+    fn read(&self) -> Result<String> {
+        fs::read_to_string(&self.path)
+            .map_err(|err| format!("{}: {err}", self.path.display()).into())
+    }
+
+    fn weighted<const DI: bool>(&self) -> Result<WeightedGraph<DI>> {
+        let content = self.read()?;
+
+        let edge_list = match self.format() {
+            Format::EdgeList if self.weighted => EdgeList::<usize, f64, DI>::from_str(&content)?,
+            Format::EdgeList => return Err("edge list files need --weighted".into()),
+            Format::Graphml => GraphMl::from_str(&content)?.weighted::<DI>(&self.cost)?,
+            Format::Tsplib if !DI => {
+                let graph =
+                    WeightedGraph::<false>::try_from(Tsplib::from_str(&content)?.edge_list)?;
+                EdgeList::from_graph(&graph)
+            }
+            Format::Tsplib => return Err("TSPLIB instances are undirected".into()),
+            Format::Dimacs => return Err("DIMACS files hold flow instances".into()),
+            Format::MatrixMarket => self.matrix_reader().matrix_market::<DI>(&content)?,
+            Format::Matrix => self.matrix_reader().plain::<DI>(&content)?,
+        };
+
+        Ok(AdjacencyList::try_from(edge_list)?)
+    }
+
+    /// Loads a flow instance and the cost of the lower bounds, which DIMACS files ship in advance
+    fn balanced(&self) -> Result<(FlowGraph, f64)> {
+        if !self.directed {
+            return Err("flow instances need --directed".into());
+        }
+
+        let content = self.read()?;
+
+        let mut cost_offset = 0.0;
+        let edge_list = match self.format() {
+            Format::EdgeList if self.balanced => EdgeList::from_str(&content)?,
+            Format::EdgeList => return Err("edge list files need --balanced".into()),
+            Format::Dimacs => {
+                let dimacs = Dimacs::from_str(&content)?;
+                cost_offset = dimacs.cost_offset;
+                dimacs.edge_list
+            }
+            Format::Graphml => GraphMl::from_str(&content)?.balanced::<true>(
+                &self.balance,
+                &self.capacity,
+                &self.cost,
+            )?,
+            Format::Tsplib | Format::MatrixMarket | Format::Matrix => {
+                return Err("distance matrices hold no flows".into())
+            }
+        };
+
+        Ok((FlowGraph::try_from(edge_list)?, cost_offset))
+    }
+
+    fn matrix_reader(&self) -> MatrixReader {
+        let mut reader = MatrixReader::new();
+        if let Some(no_edge) = self.no_edge {
+            reader.no_edge(no_edge);
+        }
+        reader
+    }
+
+    /// Loads the capacities of balanced graphs or uses the weights of weighted graphs
+    fn flow(&self) -> Result<(FlowGraph, Option<usize>, Option<usize>)> {
+        if let Format::Dimacs = self.format() {
+            let dimacs = Dimacs::from_str(&self.read()?)?;
+            if dimacs.problem == Problem::Max {
+                let graph = FlowGraph::try_from(dimacs.edge_list)?;
+                return Ok((graph, dimacs.source, dimacs.sink));
+            }
+        }
+
+        if self.balanced || !self.directed {
+            return Ok((self.balanced()?.0, None, None));
+        }
+
+        let graph = self.weighted::<true>()?;
+        let nodes = (0..graph.node_count()).map(|node| BalancedNode::new(node, 0.0));
+        let mut flow_graph = FlowGraph::with_nodes(nodes);
+
+        for edge in graph.iter_edges() {
+            let weight = FlowWeight::new(*edge.weight, 0.0, 0.0);
+            flow_graph.insert_edge(edge.from(), edge.to(), weight);
+        }
+
+        Ok((flow_graph, None, None))
+    }
 }
 
-pub fn prim() {
-    let edge_list = EdgeList::with(
-        [
-            (1, 2, 0.2),
-            (1, 3, 0.3),
-            (1, 4, 0.3),
-            (2, 3, 0.4),
-            (2, 5, 0.3),
-            (3, 4, 0.5),
-            (3, 5, 0.1),
-            (4, 6, 0.7),
-            (5, 6, 0.8),
-            (6, 0, 0.9),
-        ]
-        .into_iter(),
-        7,
-    );
-    let graph = AdjacencyList::<usize, f64>::try_from(edge_list).unwrap();
-    let total = graph.prim();
-
-    assert_eq!(total, 2.5);
+fn node_id<G: Index + Count>(graph: &G, node: usize) -> Result<NodeId<G::Id>> {
+    graph
+        .node_ids()
+        .nth(node)
+        .ok_or_else(|| format!("node {node} is not in the graph").into())
 }
 
-pub fn graph_gross() -> Result<(), Box<dyn std::error::Error>> {
-    let edge_list = fs::read_to_string("data/Graph_ganzgross.txt")?;
-    let edge_list = EdgeList::from_str(&edge_list).unwrap();
-    let graph = AdjacencyList::<usize, ()>::try_from(edge_list).unwrap();
+/// The outcome of a single command
+struct Report {
+    command: &'static str,
+    path: PathBuf,
+    value: Option<f64>,
+    route: Option<Vec<usize>>,
+    elapsed: Duration,
+}
 
-    let now = Instant::now();
-    let counter = graph.bfs_scc().len();
-    let elapsed = now.elapsed();
+impl Report {
+    fn new(command: &'static str, path: &Path) -> Self {
+        Self {
+            command,
+            path: path.to_path_buf(),
+            value: None,
+            route: None,
+            elapsed: Duration::ZERO,
+        }
+    }
+
+    /// Runs and times `f`, which returns the value and an optional route
+    fn run(mut self, f: impl FnOnce() -> (Option<f64>, Option<Vec<usize>>)) -> Self {
+        let now = Instant::now();
+        (self.value, self.route) = f();
+        self.elapsed = now.elapsed();
+        self
+    }
+
+    fn print(&self, json: bool) {
+        if json {
+            let report = json!({
+                "command": self.command,
+                "path": self.path,
+                "value": self.value,
+                "route": self.route,
+                "seconds": self.elapsed.as_secs_f64(),
+            });
+            println!("{report}");
+            return;
+        }
+
+        let value = match self.value {
+            Some(value) => value.to_string(),
+            None => "none".to_string(),
+        };
+        println!(
+            "{}: {}: {value} in {:?}",
+            self.command,
+            self.path.display(),
+            self.elapsed
+        );
+
+        if let Some(route) = &self.route {
+            let route = route
+                .iter()
+                .map(|node| node.to_string())
+                .collect::<Vec<_>>();
+            println!("route: {}", route.join(" "));
+        }
+    }
+}
 
-    println!("Counter: {counter} in {:?}", elapsed);
+fn tsp<const DI: bool>(input: &Input, algo: Tsp) -> Result<Report> {
+    let graph = input.weighted::<DI>()?;
+
+    Ok(Report::new("tsp", &input.path).run(|| {
+        let tour = match algo {
+            Tsp::Nn => nearest_neighbor_from_first(&graph),
+            Tsp::Dt => double_tree(&graph),
+            Tsp::Bb => branch_bound(&graph),
+            Tsp::Bf => brute_force(&graph),
+        };
+
+        match tour {
+            Some((route, total)) => {
+                let nodes = route.node_ids().iter().map(|node| node.as_usize());
+                (Some(total), Some(nodes.collect()))
+            }
+            None => (None, None),
+        }
+    }))
+}
 
-    Ok(())
+fn sp<const DI: bool>(input: &Input, from: usize, to: usize, algo: Sp) -> Result<Report> {
+    let graph = input.weighted::<DI>()?;
+    let (from, to) = (node_id(&graph, from)?, node_id(&graph, to)?);
+
+    Ok(Report::new("sp", &input.path).run(|| {
+        let distance = match algo {
+            Sp::Dijkstra => dijkstra_between(&graph, from, to),
+            Sp::BellmanFord => bellman_ford_between(&graph, from, to),
+        };
+        (distance, None)
+    }))
 }
 
-pub fn depth_search(path: &str) {
-    let edge_list = fs::read_to_string(path).unwrap();
-    let edge_list = EdgeList::from_str(&edge_list).unwrap();
-    let graph = AdjacencyList::<usize, f64>::try_from(edge_list).unwrap();
+fn run(command: &Command) -> Result<Report> {
+    match command {
+        Command::Components { input } => {
+            if input.directed {
+                return Err("components are counted in undirected graphs".into());
+            }
+
+            let report = Report::new("components", &input.path);
+            if input.weighted || !matches!(input.format(), Format::EdgeList) {
+                let graph = input.weighted::<false>()?;
+                Ok(report.run(|| (Some(bfs_scc(&graph).len() as f64), None)))
+            } else {
+                let edge_list = EdgeList::<usize, ()>::from_str(&input.read()?)?;
+                let graph = AdjacencyList::<usize, ()>::try_from(edge_list)?;
+                Ok(report.run(|| (Some(bfs_scc(&graph).len() as f64), None)))
+            }
+        }
+        Command::Mst { input, algo } => {
+            if input.directed {
+                return Err("spanning trees are computed in undirected graphs".into());
+            }
+
+            let graph = input.weighted::<false>()?;
+            Ok(Report::new("mst", &input.path).run(|| {
+                let total = match algo {
+                    Mst::Kruskal => kruskal(&graph).1,
+                    Mst::Prim => prim(&graph),
+                };
+                (Some(total), None)
+            }))
+        }
+        Command::Sp {
+            input,
+            from,
+            to,
+            algo,
+        } => match input.directed {
+            true => sp::<true>(input, *from, *to, *algo),
+            false => sp::<false>(input, *from, *to, *algo),
+        },
+        Command::Tsp { input, algo } => match input.directed {
+            true => tsp::<true>(input, *algo),
+            false => tsp::<false>(input, *algo),
+        },
+        Command::Maxflow {
+            input,
+            source,
+            sink,
+        } => {
+            let (graph, default_source, default_sink) = input.flow()?;
+            let source = source.or(default_source).ok_or("missing --source")?;
+            let sink = sink.or(default_sink).ok_or("missing --sink")?;
+            let (source, sink) = (node_id(&graph, source)?, node_id(&graph, sink)?);
+
+            Ok(Report::new("maxflow", &input.path).run(|| {
+                let total = capacity_scaling(&graph, source, sink).map(|(_, total)| total);
+                (total, None)
+            }))
+        }
+        Command::Mcf { input } => {
+            let (graph, cost_offset) = input.balanced()?;
+
+            Ok(Report::new("mcf", &input.path).run(|| {
+                // the balances can not be satisfied by any flow
+                if !Mcf::init(&graph).solvable() {
+                    return (None, None);
+                }
+
+                (Some(cycle_canceling(&graph) + cost_offset), None)
+            }))
+        }
+    }
+}
 
-    let connected_components = graph.dfs_scc().len();
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    run(&cli.command)?.print(cli.json);
 
-    println!("Connected Components: {connected_components}");
+    Ok(())
 }
@@ -0,0 +1,520 @@
+use crate::{
+    edge_list::{EdgeList, Tokens},
+    error::{GraphError, GraphResult, ParseErrorKind},
+    structures::SparseMatrixBuilder,
+};
+use std::collections::{HashMap, HashSet};
+
+/// Reads square distance matrices in the Matrix Market or a plain whitespace format.
+/// Symmetric matrices store only their lower triangle, in directed graphs the entries
+/// are mirrored. Undirected graphs keep one edge per pair, so the entries of a general
+/// matrix have to match their mirrored entries.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct MatrixReader {
+    no_edge: Option<f64>,
+}
+
+impl MatrixReader {
+    /// Every entry becomes an edge, including the diagonal
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Entries equal to `value` are no edges, `0.0` and `f64::INFINITY` are common choices
+    pub fn no_edge(&mut self, value: f64) -> &mut Self {
+        self.no_edge = Some(value);
+        self
+    }
+
+    /// Reads a `%%MatrixMarket matrix` file in the coordinate or array format.
+    /// The `pattern` field gives every stored entry the weight one.
+    pub fn matrix_market<const DI: bool>(&self, s: &str) -> GraphResult<EdgeList<usize, f64, DI>> {
+        let mut lines = Tokens::lines(s);
+
+        let mut header = lines.next().unwrap_or_else(|| Tokens::new(1, ""));
+        let banner = header.token()?;
+        if banner != "%%MatrixMarket" {
+            let kind = ParseErrorKind::Expected("the `%%MatrixMarket` banner");
+            return Err(header.error(Some(banner), kind));
+        }
+
+        let object = header.token()?;
+        if !object.eq_ignore_ascii_case("matrix") {
+            return Err(header.error(Some(object), ParseErrorKind::Expected("`matrix`")));
+        }
+
+        let format = header.token()?;
+        let coordinate = match format.to_ascii_lowercase().as_str() {
+            "coordinate" => true,
+            "array" => false,
+            _ => {
+                let kind = ParseErrorKind::Expected("`coordinate` or `array`");
+                return Err(header.error(Some(format), kind));
+            }
+        };
+
+        let field = header.token()?;
+        let pattern = match field.to_ascii_lowercase().as_str() {
+            "real" | "double" | "integer" => false,
+            "pattern" if coordinate => true,
+            _ => {
+                let kind = ParseErrorKind::Expected("`real`, `integer` or a coordinate `pattern`");
+                return Err(header.error(Some(field), kind));
+            }
+        };
+
+        let symmetry = header.token()?;
+        let symmetric = match symmetry.to_ascii_lowercase().as_str() {
+            "general" => false,
+            "symmetric" => true,
+            _ => {
+                let kind = ParseErrorKind::Expected("`general` or `symmetric`");
+                return Err(header.error(Some(symmetry), kind));
+            }
+        };
+
+        let mut lines = lines.filter(|tokens| tokens.len() > 0 && !tokens.text.starts_with('%'));
+        let end = s.lines().count() + 1;
+
+        let mut size = lines.next().unwrap_or_else(|| Tokens::new(end, ""));
+        let node_count = size.parse::<usize>()?;
+        let token = size.token()?;
+        if size.value::<usize>(token)? != node_count {
+            return Err(size.error(Some(token), ParseErrorKind::Expected("a square matrix")));
+        }
+
+        let mut entries = Vec::new();
+
+        if coordinate {
+            let expected = size.parse::<usize>()?;
+            let mut seen = HashSet::new();
+
+            for mut tokens in lines {
+                let from = parse_node(&mut tokens, node_count)?;
+                let to = parse_node(&mut tokens, node_count)?;
+
+                // symmetric matrices hold every pair once, in either triangle
+                let position = match symmetric {
+                    true => (from.max(to), from.min(to)),
+                    false => (from, to),
+                };
+                if !seen.insert(position) {
+                    return Err(GraphError::EdgeAlreadyExists {
+                        from,
+                        to,
+                        line: Some(tokens.line),
+                    });
+                }
+
+                let token = if pattern { None } else { Some(tokens.token()?) };
+                let weight = match token {
+                    Some(token) => tokens.value::<f64>(token)?,
+                    None => 1.0,
+                };
+                entries.push(Entry::new(from, to, weight, &tokens, token));
+            }
+
+            if entries.len() != expected {
+                let kind = ParseErrorKind::ArcCount {
+                    expected,
+                    found: entries.len(),
+                };
+                return Err(size.error(size.text.split_whitespace().last(), kind));
+            }
+        } else {
+            // the values are listed column by column, symmetric ones from the diagonal down
+            let mut positions = (0..node_count)
+                .flat_map(|j| (if symmetric { j } else { 0 }..node_count).map(move |i| (i, j)))
+                .peekable();
+
+            while positions.peek().is_some() {
+                let Some(mut tokens) = lines.next() else {
+                    let end = Tokens::new(end, "");
+                    return Err(end.error(None, ParseErrorKind::MissingToken));
+                };
+                for _ in 0..tokens.len() {
+                    let token = tokens.token()?;
+                    let Some((i, j)) = positions.next() else {
+                        let kind = ParseErrorKind::Expected("the end of the matrix");
+                        return Err(tokens.error(Some(token), kind));
+                    };
+                    let weight = tokens.value::<f64>(token)?;
+                    entries.push(Entry::new(i, j, weight, &tokens, Some(token)));
+                }
+            }
+
+            if let Some(mut tokens) = lines.next() {
+                let token = tokens.token()?;
+                let kind = ParseErrorKind::Expected("the end of the matrix");
+                return Err(tokens.error(Some(token), kind));
+            }
+        }
+
+        self.edge_list(node_count, entries, symmetric)
+    }
+
+    /// Reads one row of the matrix per line.
+    /// If the first row holds a single value, the rows form the lower triangle of a symmetric matrix.
+    pub fn plain<const DI: bool>(&self, s: &str) -> GraphResult<EdgeList<usize, f64, DI>> {
+        let rows = Tokens::lines(s)
+            .filter(|tokens| tokens.len() > 0)
+            .collect::<Vec<_>>();
+        let node_count = rows.len();
+        let symmetric = node_count > 1 && rows[0].len() == 1;
+
+        let mut entries = Vec::new();
+        for (i, mut tokens) in rows.into_iter().enumerate() {
+            let expected = if symmetric { i + 1 } else { node_count };
+            let found = tokens.len();
+            if found != expected {
+                let first = tokens.text.split_whitespace().next();
+                return Err(tokens.error(first, ParseErrorKind::RowLength { expected, found }));
+            }
+
+            for j in 0..found {
+                let token = tokens.token()?;
+                let weight = tokens.value::<f64>(token)?;
+                entries.push(Entry::new(i, j, weight, &tokens, Some(token)));
+            }
+        }
+
+        self.edge_list(node_count, entries, symmetric)
+    }
+
+    /// Fails if an undirected graph is read from a general matrix which is not symmetric
+    fn edge_list<const DI: bool>(
+        &self,
+        node_count: usize,
+        entries: Vec<Entry>,
+        symmetric: bool,
+    ) -> GraphResult<EdgeList<usize, f64, DI>> {
+        if !DI && !symmetric {
+            let mut weights = HashMap::new();
+
+            for entry in &entries {
+                let pair = (entry.row.min(entry.col), entry.row.max(entry.col));
+
+                if let Some(&weight) = weights.get(&pair)
+                    && weight != entry.weight
+                {
+                    let kind = ParseErrorKind::Expected("the value of the mirrored entry");
+                    return Err(entry.error(kind));
+                }
+
+                weights.insert(pair, entry.weight);
+            }
+        }
+
+        let mut edges = SparseMatrixBuilder::with_capacity(node_count, node_count);
+
+        for entry in entries {
+            let (i, j, weight) = (entry.row, entry.col, entry.weight);
+
+            if self.no_edge == Some(weight) {
+                continue;
+            }
+
+            if !DI {
+                edges.insert(i.min(j), i.max(j), weight);
+            } else {
+                edges.insert(i, j, weight);
+                if symmetric && i != j {
+                    edges.insert(j, i, weight);
+                }
+            }
+        }
+
+        // the mirrored entries of undirected graphs have the same weight
+        Ok(EdgeList {
+            nodes: (0..node_count).collect(),
+            edges: edges.build(|_, _| {}),
+            node_count,
+        })
+    }
+}
+
+/// A value of the matrix and the location of its token
+struct Entry<'a> {
+    row: usize,
+    col: usize,
+    weight: f64,
+    line: usize,
+    text: &'a str,
+    token: Option<&'a str>,
+}
+
+impl<'a> Entry<'a> {
+    fn new(
+        row: usize,
+        col: usize,
+        weight: f64,
+        tokens: &Tokens<'a>,
+        token: Option<&'a str>,
+    ) -> Self {
+        Self {
+            row,
+            col,
+            weight,
+            line: tokens.line,
+            text: tokens.text,
+            token,
+        }
+    }
+
+    fn error(&self, kind: ParseErrorKind) -> GraphError {
+        Tokens::new(self.line, self.text).error(self.token, kind)
+    }
+}
+
+/// Parses a row or column index starting at one
+fn parse_node(tokens: &mut Tokens, node_count: usize) -> GraphResult<usize> {
+    let token = tokens.token()?;
+    let node = tokens.value::<usize>(token)?;
+
+    if node == 0 || node > node_count {
+        return Err(tokens.error(Some(token), ParseErrorKind::NodeOutOfRange { node_count }));
+    }
+
+    Ok(node - 1)
+}
+
+#[cfg(test)]
+mod test {
+    use super::MatrixReader;
+    use crate::{
+        error::{GraphError, ParseErrorKind},
+        graph::{Base, Count, Get},
+        prelude::*,
+        test::id,
+    };
+
+    const COORDINATE: &str = "%%MatrixMarket matrix coordinate real symmetric
+% distances of four nodes
+4 4 5
+1 1 0
+2 1 3.5
+3 1 2
+4 2 1
+4 3 7
+";
+
+    const FULL: &str = "0 3.5 2 inf
+3.5 0 inf 1
+2 inf 0 7
+inf 1 7 0
+";
+
+    fn weight<G: Get + Base<Id = usize, Weight = f64>>(
+        graph: &G,
+        from: usize,
+        to: usize,
+    ) -> Option<f64> {
+        graph
+            .weight(EdgeId::new_unchecked(id(from), id(to)))
+            .copied()
+    }
+
+    #[test]
+    fn matrix_market_coordinate_symmetric() {
+        let edge_list = MatrixReader::new()
+            .no_edge(0.0)
+            .matrix_market::<true>(COORDINATE)
+            .unwrap();
+        let graph = AdjacencyMatrix::<usize, f64, true>::try_from(edge_list).unwrap();
+
+        assert_eq!(graph.edge_count(), 8);
+        assert_eq!(weight(&graph, 0, 1), Some(3.5));
+        assert_eq!(weight(&graph, 1, 0), Some(3.5));
+        assert_eq!(weight(&graph, 0, 0), None);
+
+        let edge_list = MatrixReader::new()
+            .matrix_market::<false>(COORDINATE)
+            .unwrap();
+        let graph = AdjacencyList::<usize, f64>::try_from(edge_list).unwrap();
+        assert_eq!(weight(&graph, 3, 2), Some(7.0));
+        assert_eq!(weight(&graph, 0, 0), Some(0.0));
+    }
+
+    #[test]
+    fn matrix_market_array_matches_plain() {
+        let array = "%%MatrixMarket matrix array real symmetric
+4 4
+0
+3.5
+2
+inf
+0
+inf
+1
+0
+7
+0
+";
+        let mut reader = MatrixReader::new();
+        reader.no_edge(f64::INFINITY);
+
+        let array = reader.matrix_market::<false>(array).unwrap();
+        let full = reader.plain::<false>(FULL).unwrap();
+        let lower = reader
+            .plain::<false>("0\n3.5 0\n2 inf 0\ninf 1 7 0\n")
+            .unwrap();
+
+        assert_eq!(array, full);
+        assert_eq!(lower, full);
+
+        let graph = AdjacencyMatrix::<usize, f64>::try_from(full).unwrap();
+        assert_eq!(weight(&graph, 3, 1), Some(1.0));
+        assert_eq!(weight(&graph, 0, 3), None);
+    }
+
+    #[test]
+    fn matrix_market_general_pattern() {
+        let pattern = "%%MatrixMarket matrix coordinate pattern general\n3 3 2\n1 2\n3 1\n";
+        let edge_list = MatrixReader::new().matrix_market::<true>(pattern).unwrap();
+        let graph = AdjacencyList::<usize, f64, true>::try_from(edge_list).unwrap();
+
+        assert_eq!(graph.edge_count(), 2);
+        assert_eq!(weight(&graph, 0, 1), Some(1.0));
+        assert_eq!(weight(&graph, 1, 0), None);
+        assert_eq!(weight(&graph, 2, 0), Some(1.0));
+    }
+
+    #[test]
+    fn matrix_market_errors() {
+        let reader = MatrixReader::new();
+
+        let result =
+            reader.matrix_market::<true>("%%MatrixMarket matrix array real general\n2 3\n");
+        assert!(matches!(
+            result,
+            Err(GraphError::Parse {
+                line: 2,
+                column: 3,
+                kind: ParseErrorKind::Expected(_),
+                ..
+            })
+        ));
+
+        let result = reader.matrix_market::<true>(
+            "%%MatrixMarket matrix coordinate real hermitian\n2 2 1\n1 2 1\n",
+        );
+        assert!(matches!(
+            result,
+            Err(GraphError::Parse {
+                line: 1,
+                column: 39,
+                ..
+            })
+        ));
+
+        let result = reader
+            .matrix_market::<true>("%%MatrixMarket matrix coordinate real general\n2 2 2\n1 3 1\n");
+        assert!(matches!(
+            result,
+            Err(GraphError::Parse {
+                line: 3,
+                kind: ParseErrorKind::NodeOutOfRange { node_count: 2 },
+                ..
+            })
+        ));
+
+        let result = reader
+            .matrix_market::<true>("%%MatrixMarket matrix coordinate real general\n2 2 2\n1 2 1\n");
+        assert!(matches!(
+            result,
+            Err(GraphError::Parse {
+                line: 2,
+                kind: ParseErrorKind::ArcCount {
+                    expected: 2,
+                    found: 1
+                },
+                ..
+            })
+        ));
+
+        let result = reader.matrix_market::<true>(
+            "%%MatrixMarket matrix coordinate real general\n2 2 2\n1 2 1\n1 2 3\n",
+        );
+        assert!(matches!(
+            result,
+            Err(GraphError::EdgeAlreadyExists {
+                from: 0,
+                to: 1,
+                line: Some(4)
+            })
+        ));
+
+        let result = reader.matrix_market::<true>(
+            "%%MatrixMarket matrix coordinate real symmetric\n2 2 2\n2 1 1\n1 2 1\n",
+        );
+        assert!(matches!(
+            result,
+            Err(GraphError::EdgeAlreadyExists { line: Some(4), .. })
+        ));
+
+        let result = reader
+            .matrix_market::<false>("%%MatrixMarket matrix array real general\n2 2\n0 1\n2 0\n");
+        assert!(matches!(
+            result,
+            Err(GraphError::Parse {
+                line: 4,
+                column: 1,
+                kind: ParseErrorKind::Expected(_),
+                ..
+            })
+        ));
+
+        // the same matrix is fine for directed graphs
+        let result = reader
+            .matrix_market::<true>("%%MatrixMarket matrix array real general\n2 2\n0 1\n2 0\n");
+        assert!(result.is_ok());
+
+        let result = reader
+            .matrix_market::<true>("%%MatrixMarket matrix array real general\n2 2\n0 1\n2 0 5\n");
+        assert!(matches!(
+            result,
+            Err(GraphError::Parse {
+                line: 4,
+                column: 5,
+                kind: ParseErrorKind::Expected(_),
+                ..
+            })
+        ));
+
+        let result = reader
+            .matrix_market::<true>("%%MatrixMarket matrix array real general\n2 2\n0 1\n2 0\n5\n");
+        assert!(matches!(
+            result,
+            Err(GraphError::Parse {
+                line: 5,
+                column: 1,
+                kind: ParseErrorKind::Expected(_),
+                ..
+            })
+        ));
+
+        let result = reader.plain::<false>("0 1\n2 0\n");
+        assert!(matches!(
+            result,
+            Err(GraphError::Parse {
+                line: 2,
+                column: 1,
+                kind: ParseErrorKind::Expected(_),
+                ..
+            })
+        ));
+
+        let result = reader.plain::<true>("0 1 2\n1 0\n2 1 0\n");
+        assert!(matches!(
+            result,
+            Err(GraphError::Parse {
+                line: 2,
+                kind: ParseErrorKind::RowLength {
+                    expected: 3,
+                    found: 2
+                },
+                ..
+            })
+        ));
+    }
+}
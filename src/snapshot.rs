@@ -0,0 +1,415 @@
+use crate::{
+    edge_list::EdgeList,
+    error::{GraphError, GraphResult},
+    graph::{BalancedNode, FlowWeight},
+    prelude::{AdjacencyList, AdjacencyMatrix},
+    structures::SparseMatrix,
+};
+use std::{
+    fs::File,
+    io::{Read, Write},
+    path::Path,
+};
+
+const MAGIC: &[u8; 4] = b"GRPH";
+const VERSION: u16 = 1;
+
+/// Magic, version, directedness, node and weight tags, node count and edge count
+const HEADER: usize = 4 + 2 + 1 + 1 + 1 + 8 + 8;
+const CHECKSUM: usize = 8;
+
+/// Values with a fixed size little endian encoding
+pub trait SnapshotValue: Sized {
+    /// Identifies the type, snapshots with another tag are rejected
+    const TAG: u8;
+    const SIZE: usize;
+
+    fn write(&self, bytes: &mut Vec<u8>);
+    /// Reads the value from exactly `SIZE` bytes
+    fn read(bytes: &[u8]) -> Self;
+}
+
+impl SnapshotValue for () {
+    const TAG: u8 = 0;
+    const SIZE: usize = 0;
+
+    fn write(&self, _: &mut Vec<u8>) {}
+
+    fn read(_: &[u8]) -> Self {}
+}
+
+impl SnapshotValue for usize {
+    const TAG: u8 = 1;
+    const SIZE: usize = 8;
+
+    fn write(&self, bytes: &mut Vec<u8>) {
+        bytes.extend_from_slice(&(*self as u64).to_le_bytes());
+    }
+
+    fn read(bytes: &[u8]) -> Self {
+        u64::from_le_bytes(bytes.try_into().unwrap()) as usize
+    }
+}
+
+impl SnapshotValue for f32 {
+    const TAG: u8 = 2;
+    const SIZE: usize = 4;
+
+    fn write(&self, bytes: &mut Vec<u8>) {
+        bytes.extend_from_slice(&self.to_le_bytes());
+    }
+
+    fn read(bytes: &[u8]) -> Self {
+        f32::from_le_bytes(bytes.try_into().unwrap())
+    }
+}
+
+impl SnapshotValue for f64 {
+    const TAG: u8 = 3;
+    const SIZE: usize = 8;
+
+    fn write(&self, bytes: &mut Vec<u8>) {
+        bytes.extend_from_slice(&self.to_le_bytes());
+    }
+
+    fn read(bytes: &[u8]) -> Self {
+        f64::from_le_bytes(bytes.try_into().unwrap())
+    }
+}
+
+impl SnapshotValue for BalancedNode<usize, f64> {
+    const TAG: u8 = 4;
+    const SIZE: usize = 16;
+
+    fn write(&self, bytes: &mut Vec<u8>) {
+        self.node.write(bytes);
+        self.balance.write(bytes);
+    }
+
+    fn read(bytes: &[u8]) -> Self {
+        BalancedNode::new(usize::read(&bytes[..8]), f64::read(&bytes[8..]))
+    }
+}
+
+impl SnapshotValue for FlowWeight<f64> {
+    const TAG: u8 = 5;
+    const SIZE: usize = 24;
+
+    fn write(&self, bytes: &mut Vec<u8>) {
+        self.capacity.write(bytes);
+        self.cost.write(bytes);
+        self.flow.write(bytes);
+    }
+
+    fn read(bytes: &[u8]) -> Self {
+        FlowWeight::new(
+            f64::read(&bytes[..8]),
+            f64::read(&bytes[8..16]),
+            f64::read(&bytes[16..24]),
+        )
+    }
+}
+
+/// FNV-1a, enough to detect truncated or damaged files
+fn checksum(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, &byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+fn mismatch(field: &'static str, expected: u8, found: u8) -> GraphResult<()> {
+    match expected == found {
+        true => Ok(()),
+        false => Err(GraphError::SnapshotMismatch {
+            field,
+            expected,
+            found,
+        }),
+    }
+}
+
+/// Splits off the next `len` bytes
+fn take<'a>(bytes: &mut &'a [u8], len: usize) -> GraphResult<&'a [u8]> {
+    if bytes.len() < len {
+        return Err(GraphError::InvalidSnapshot("the file is truncated"));
+    }
+
+    let (head, tail) = bytes.split_at(len);
+    *bytes = tail;
+    Ok(head)
+}
+
+fn take_values<T: SnapshotValue>(bytes: &mut &[u8], count: usize) -> GraphResult<Vec<T>> {
+    let len = count
+        .checked_mul(T::SIZE)
+        .ok_or(GraphError::InvalidSnapshot("the counts are too large"))?;
+    let values = take(bytes, len)?;
+
+    if T::SIZE == 0 {
+        return Ok((0..count).map(|_| T::read(&[])).collect());
+    }
+
+    Ok(values.chunks_exact(T::SIZE).map(T::read).collect())
+}
+
+/// A versioned binary image of a graph, loaded without parsing any text.
+/// The edges are stored in compressed sparse rows like in `EdgeList`, undirected edges once,
+/// so a snapshot of one graph type can be loaded as another with the same `DI` and types.
+/// Parallel edges are kept.
+pub trait Snapshot: Sized {
+    fn write_snapshot(&self, writer: impl Write) -> GraphResult<()>;
+    /// Fails if the file is damaged or was written with another direction, node or weight type,
+    /// or with [GraphError::EdgeAlreadyExists] if the graph can not hold its parallel edges
+    fn read_snapshot(reader: impl Read) -> GraphResult<Self>;
+
+    fn save(&self, path: impl AsRef<Path>) -> GraphResult<()> {
+        self.write_snapshot(File::create(path)?)
+    }
+
+    fn load(path: impl AsRef<Path>) -> GraphResult<Self> {
+        Self::read_snapshot(File::open(path)?)
+    }
+}
+
+impl<N, W, const DI: bool> Snapshot for EdgeList<N, W, DI>
+where
+    N: SnapshotValue,
+    W: SnapshotValue,
+{
+    fn write_snapshot(&self, mut writer: impl Write) -> GraphResult<()> {
+        let (offsets, col_indices, values) = self.edges.raw_parts();
+        let node_count = self.nodes.len();
+        let edge_count = values.len();
+
+        let size = HEADER
+            + node_count * N::SIZE
+            + (offsets.len() + col_indices.len()) * 8
+            + edge_count * W::SIZE
+            + CHECKSUM;
+        let mut bytes = Vec::with_capacity(size);
+
+        bytes.extend_from_slice(MAGIC);
+        bytes.extend_from_slice(&VERSION.to_le_bytes());
+        bytes.extend_from_slice(&[DI as u8, N::TAG, W::TAG]);
+        node_count.write(&mut bytes);
+        edge_count.write(&mut bytes);
+
+        self.nodes.iter().for_each(|node| node.write(&mut bytes));
+        offsets.iter().for_each(|offset| offset.write(&mut bytes));
+        col_indices.iter().for_each(|col| col.write(&mut bytes));
+        values.iter().for_each(|weight| weight.write(&mut bytes));
+
+        bytes.extend_from_slice(&checksum(&bytes).to_le_bytes());
+        writer.write_all(&bytes)?;
+        writer.flush()?;
+
+        Ok(())
+    }
+
+    fn read_snapshot(mut reader: impl Read) -> GraphResult<Self> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes)?;
+
+        if bytes.len() < HEADER + CHECKSUM || &bytes[..4] != MAGIC {
+            return Err(GraphError::InvalidSnapshot("the file is no graph snapshot"));
+        }
+
+        let (content, expected) = bytes.split_at(bytes.len() - CHECKSUM);
+        if checksum(content).to_le_bytes() != expected {
+            return Err(GraphError::InvalidSnapshot("the checksum does not match"));
+        }
+
+        let mut content = &content[4..];
+        let version = u16::from_le_bytes(take(&mut content, 2)?.try_into().unwrap());
+        if version != VERSION {
+            return Err(GraphError::InvalidSnapshot("the version is not supported"));
+        }
+
+        let tags = take(&mut content, 3)?;
+        mismatch("directedness", DI as u8, tags[0])?;
+        mismatch("node type", N::TAG, tags[1])?;
+        mismatch("weight type", W::TAG, tags[2])?;
+
+        let node_count = usize::read(take(&mut content, 8)?);
+        let edge_count = usize::read(take(&mut content, 8)?);
+
+        let nodes = take_values::<N>(&mut content, node_count)?;
+        let offsets = take_values::<usize>(&mut content, node_count.saturating_add(1))?;
+        let col_indices = take_values::<usize>(&mut content, edge_count)?;
+        let values = take_values::<W>(&mut content, edge_count)?;
+
+        if !content.is_empty() {
+            return Err(GraphError::InvalidSnapshot("the file has trailing bytes"));
+        }
+
+        let edges = SparseMatrix::from_raw_parts(offsets, col_indices, values, node_count)
+            .ok_or(GraphError::InvalidSnapshot("the edges are out of place"))?;
+
+        Ok(Self {
+            nodes,
+            edges,
+            node_count,
+        })
+    }
+}
+
+impl<N, W, const DI: bool, const MULTI: bool> Snapshot for AdjacencyList<N, W, DI, MULTI>
+where
+    N: SnapshotValue + Clone,
+    W: SnapshotValue + Copy,
+{
+    fn write_snapshot(&self, writer: impl Write) -> GraphResult<()> {
+        EdgeList::from(self).write_snapshot(writer)
+    }
+
+    fn read_snapshot(reader: impl Read) -> GraphResult<Self> {
+        Self::try_from(EdgeList::read_snapshot(reader)?)
+    }
+}
+
+impl<N, W, const DI: bool> Snapshot for AdjacencyMatrix<N, W, DI>
+where
+    N: SnapshotValue + Clone,
+    W: SnapshotValue + Copy,
+{
+    fn write_snapshot(&self, writer: impl Write) -> GraphResult<()> {
+        EdgeList::from(self).write_snapshot(writer)
+    }
+
+    fn read_snapshot(reader: impl Read) -> GraphResult<Self> {
+        Self::try_from(EdgeList::read_snapshot(reader)?)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    extern crate test;
+
+    use super::Snapshot;
+    use crate::{
+        algorithms::{cycle_canceling, dijkstra_between},
+        error::GraphError,
+        graph::{BalancedNode, Count, Create, FlowWeight, Get, Insert},
+        prelude::*,
+        test::{bgraph, digraph, id, undigraph},
+    };
+    use std::{fs, str::FromStr};
+    use test::Bencher;
+
+    fn snapshot<G: Snapshot>(graph: &G) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        graph.write_snapshot(&mut bytes).unwrap();
+        bytes
+    }
+
+    #[test]
+    fn snapshot_edge_list_round_trip() {
+        let content = fs::read_to_string("data/G_1_2.txt").unwrap();
+        let edge_list = EdgeList::<usize, f64, true>::from_str(&content).unwrap();
+
+        let bytes = snapshot(&edge_list);
+        assert_eq!(EdgeList::read_snapshot(&bytes[..]).unwrap(), edge_list);
+    }
+
+    #[test]
+    fn snapshot_graph_round_trip() {
+        let graph: AdjacencyList<usize, f64> = undigraph("data/G_1_2.txt").unwrap();
+        let bytes = snapshot(&graph);
+
+        let adj_list = AdjacencyList::<usize, f64>::read_snapshot(&bytes[..]).unwrap();
+        let adj_mat = AdjacencyMatrix::<usize, f64>::read_snapshot(&bytes[..]).unwrap();
+
+        assert_eq!(adj_list.edge_count(), graph.edge_count());
+        assert_eq!(adj_mat.edge_count(), graph.edge_count());
+        assert_eq!(
+            dijkstra_between(&adj_mat, id(0), id(1)),
+            dijkstra_between(&graph, id(0), id(1))
+        );
+
+        let graph: AdjacencyList<BalancedNode<usize, f64>, FlowWeight<f64>, true> =
+            bgraph("data/Kostenminimal1.txt").unwrap();
+        let graph: AdjacencyList<BalancedNode<usize, f64>, FlowWeight<f64>, true> =
+            AdjacencyList::read_snapshot(&snapshot(&graph)[..]).unwrap();
+        assert_eq!(cycle_canceling(&graph), 3.0);
+    }
+
+    #[test]
+    fn snapshot_rejects_other_types() {
+        let graph: AdjacencyList<usize, f64, true> = digraph("data/Wege1.txt").unwrap();
+        let bytes = snapshot(&graph);
+
+        assert!(matches!(
+            AdjacencyList::<usize, f64, false>::read_snapshot(&bytes[..]),
+            Err(GraphError::SnapshotMismatch {
+                field: "directedness",
+                expected: 0,
+                found: 1
+            })
+        ));
+        assert!(matches!(
+            AdjacencyList::<usize, f32, true>::read_snapshot(&bytes[..]),
+            Err(GraphError::SnapshotMismatch {
+                field: "weight type",
+                ..
+            })
+        ));
+
+        let mut damaged = bytes.clone();
+        damaged[30] ^= 1;
+        assert!(matches!(
+            AdjacencyList::<usize, f64, true>::read_snapshot(&damaged[..]),
+            Err(GraphError::InvalidSnapshot(_))
+        ));
+        assert!(matches!(
+            AdjacencyList::<usize, f64, true>::read_snapshot(&bytes[..bytes.len() - 1]),
+            Err(GraphError::InvalidSnapshot(_))
+        ));
+    }
+
+    #[test]
+    fn snapshot_inserted_edges() {
+        let mut graph = AdjacencyList::<usize, f64>::with_nodes(0..3);
+        graph.insert_edge(id(2), id(1), 1.0);
+        graph.insert_edge(id(0), id(0), 2.0);
+
+        let read = AdjacencyList::<usize, f64>::read_snapshot(&snapshot(&graph)[..]).unwrap();
+
+        assert_eq!(read.edge_count(), graph.edge_count());
+        assert_eq!(read.weight(EdgeId::new_unchecked(id(1), id(2))), Some(&1.0));
+        assert_eq!(read.weight(EdgeId::new_unchecked(id(2), id(1))), Some(&1.0));
+        assert_eq!(read.weight(EdgeId::new_unchecked(id(0), id(0))), Some(&2.0));
+    }
+
+    #[test]
+    fn snapshot_parallel_edges() {
+        let mut graph = AdjacencyList::<usize, f64, false, true>::with_nodes(0..2);
+        graph.insert_edge(id(0), id(1), 1.0);
+        graph.insert_edge(id(1), id(0), 2.0);
+        graph.insert_edge(id(0), id(1), 3.0);
+
+        let bytes = snapshot(&graph);
+        let read = AdjacencyList::<usize, f64, false, true>::read_snapshot(&bytes[..]).unwrap();
+
+        assert_eq!(read.edge_count(), 3);
+        assert_eq!(EdgeList::from(&read), EdgeList::from(&graph));
+        assert!(matches!(
+            AdjacencyList::<usize, f64>::read_snapshot(&bytes[..]),
+            Err(GraphError::EdgeAlreadyExists {
+                from: 0,
+                to: 1,
+                line: None
+            })
+        ));
+    }
+
+    #[bench]
+    fn snapshot_load_g_100_200(b: &mut Bencher) {
+        let graph: AdjacencyList<usize, f64> = undigraph("data/G_100_200.txt").unwrap();
+        let bytes = snapshot(&graph);
+
+        b.iter(|| {
+            let graph = AdjacencyList::<usize, f64>::read_snapshot(&bytes[..]).unwrap();
+            assert_eq!(graph.node_count(), 100_000);
+        })
+    }
+}
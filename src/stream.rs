@@ -0,0 +1,317 @@
+use crate::{
+    edge_list::Tokens,
+    error::{GraphResult, ParseErrorKind},
+    graph::{Create, Insert, Reserve},
+    prelude::{AdjacencyList, CsrGraph, NodeId},
+};
+use std::{
+    fs::File,
+    io::{BufRead, BufReader},
+    path::Path,
+};
+
+/// Weights read from the token following the endpoints of an edge
+pub trait ParseWeight: Sized {
+    /// Unweighted edges have no token
+    const TOKEN: bool = true;
+
+    fn parse_weight(token: &str) -> Result<Self, ParseErrorKind>;
+}
+
+impl ParseWeight for () {
+    const TOKEN: bool = false;
+
+    fn parse_weight(_: &str) -> Result<Self, ParseErrorKind> {
+        Ok(())
+    }
+}
+
+impl ParseWeight for f64 {
+    fn parse_weight(token: &str) -> Result<Self, ParseErrorKind> {
+        token.parse().map_err(ParseErrorKind::Float)
+    }
+}
+
+impl ParseWeight for f32 {
+    fn parse_weight(token: &str) -> Result<Self, ParseErrorKind> {
+        token.parse().map_err(ParseErrorKind::Float)
+    }
+}
+
+/// The state of a running read, handed to the progress callback
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Progress {
+    /// The node count of the header
+    pub node_count: usize,
+    pub edges: usize,
+    pub bytes: usize,
+}
+
+/// Reads edge lists line by line and builds the graph while reading,
+/// so the file is never held in memory as a whole.
+/// Repeated edges are kept like in the `FromStr` impls of `EdgeList`.
+pub struct EdgeListReader<'a, R> {
+    reader: R,
+    edge_capacity: usize,
+    interval: usize,
+    progress: Option<Box<dyn FnMut(Progress) + 'a>>,
+}
+
+impl EdgeListReader<'_, BufReader<File>> {
+    pub fn open(path: impl AsRef<Path>) -> GraphResult<Self> {
+        Ok(Self::new(BufReader::new(File::open(path)?)))
+    }
+}
+
+impl<'a, R: BufRead> EdgeListReader<'a, R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            edge_capacity: 0,
+            interval: 0,
+            progress: None,
+        }
+    }
+
+    /// Reserves room for `edges` edges, the nodes are reserved by the node count of the header
+    pub fn edge_capacity(&mut self, edges: usize) -> &mut Self {
+        self.edge_capacity = edges;
+        self
+    }
+
+    /// Calls `progress` after every `interval` edges and once at the end
+    pub fn progress(&mut self, interval: usize, progress: impl FnMut(Progress) + 'a) -> &mut Self {
+        self.interval = interval;
+        self.progress = Some(Box::new(progress));
+        self
+    }
+
+    pub fn adjacency_list<W, const DI: bool>(&mut self) -> GraphResult<AdjacencyList<usize, W, DI>>
+    where
+        W: ParseWeight + Clone,
+    {
+        let mut progress = self.header()?;
+
+        let mut graph = AdjacencyList::with_nodes(0..progress.node_count);
+        graph.reserve_edges(if DI { 1 } else { 2 } * self.edge_capacity);
+
+        self.edges(&mut progress, |from, to, weight: W| {
+            let from = NodeId::new_unchecked(from);
+            let to = NodeId::new_unchecked(to);
+            graph.insert_edge(from, to, weight);
+        })?;
+
+        Ok(graph)
+    }
+
+    /// Collects the edges once and sorts them into place by their source
+    pub fn csr_graph<W, const DI: bool>(&mut self) -> GraphResult<CsrGraph<usize, W, DI>>
+    where
+        W: ParseWeight + Clone,
+    {
+        let mut progress = self.header()?;
+
+        let mut adjacency = Vec::with_capacity(if DI { 1 } else { 2 } * self.edge_capacity);
+        self.edges(&mut progress, |from, to, weight: W| {
+            // undirected self loops are stored once
+            if !DI && from != to {
+                adjacency.push((to, from, weight.clone()));
+            }
+
+            adjacency.push((from, to, weight));
+        })?;
+
+        let nodes = (0..progress.node_count).collect();
+        Ok(CsrGraph::with_adjacency(nodes, adjacency))
+    }
+
+    /// Parses the node count in the first line
+    fn header(&mut self) -> GraphResult<Progress> {
+        let mut line = String::new();
+        let bytes = self.reader.read_line(&mut line)?;
+        let node_count = Tokens::new(1, line.trim_end()).parse()?;
+
+        Ok(Progress {
+            node_count,
+            edges: 0,
+            bytes,
+        })
+    }
+
+    /// Parses the remaining lines as `from to` followed by the weight
+    fn edges<W: ParseWeight>(
+        &mut self,
+        progress: &mut Progress,
+        mut edge: impl FnMut(usize, usize, W),
+    ) -> GraphResult<()> {
+        let mut line = String::new();
+        let mut number = 1;
+
+        loop {
+            line.clear();
+            let bytes = self.reader.read_line(&mut line)?;
+            if bytes == 0 {
+                break;
+            }
+
+            number += 1;
+            let mut tokens = Tokens::new(number, line.trim_end());
+            let from = tokens.node(progress.node_count)?;
+            let to = tokens.node(progress.node_count)?;
+
+            let weight = if W::TOKEN {
+                let token = tokens.token()?;
+                W::parse_weight(token).map_err(|kind| tokens.error(Some(token), kind))?
+            } else {
+                W::parse_weight("").map_err(|kind| tokens.error(None, kind))?
+            };
+
+            edge(from, to, weight);
+
+            progress.edges += 1;
+            progress.bytes += bytes;
+
+            // an interval of zero never matches, the edge count is at least one here
+            if let Some(callback) = &mut self.progress
+                && progress.edges.is_multiple_of(self.interval)
+            {
+                callback(*progress);
+            }
+        }
+
+        if let Some(callback) = &mut self.progress {
+            callback(*progress);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    extern crate test;
+
+    use super::{EdgeListReader, Progress};
+    use crate::{
+        algorithms::{bfs_scc, dijkstra_between},
+        error::{GraphError, ParseErrorKind},
+        graph::{Count, IndexAdjacent},
+        prelude::*,
+        test::{digraph, id, undigraph},
+    };
+    use std::io::Cursor;
+    use test::Bencher;
+
+    #[test]
+    fn stream_adjacency_list() {
+        let expected: AdjacencyList<usize, f64> = undigraph("data/G_1_2.txt").unwrap();
+        let graph = EdgeListReader::open("data/G_1_2.txt")
+            .unwrap()
+            .adjacency_list::<f64, false>()
+            .unwrap();
+
+        assert_eq!(graph.node_count(), expected.node_count());
+        assert_eq!(graph.edge_count(), expected.edge_count());
+        assert_eq!(
+            dijkstra_between(&graph, id(0), id(1)),
+            dijkstra_between(&expected, id(0), id(1))
+        );
+    }
+
+    #[test]
+    fn stream_csr_graph() {
+        let expected: AdjacencyList<usize, f64, true> = digraph("data/G_1_2.txt").unwrap();
+        let graph = EdgeListReader::open("data/G_1_2.txt")
+            .unwrap()
+            .edge_capacity(expected.edge_count())
+            .csr_graph::<f64, true>()
+            .unwrap();
+
+        assert_eq!(graph.edge_count(), expected.edge_count());
+        assert_eq!(
+            dijkstra_between(&graph, id(0), id(1)).unwrap() as f32,
+            5.56283
+        );
+    }
+
+    #[test]
+    fn stream_self_loop() {
+        let content = "2\n0 0\n0 1\n0 1\n";
+
+        let graph = EdgeListReader::new(Cursor::new(content))
+            .adjacency_list::<(), false>()
+            .unwrap();
+        assert_eq!(graph.edge_count(), 3);
+        assert_eq!(graph.adjacent_node_ids(id(0)).count(), 3);
+
+        let graph = EdgeListReader::new(Cursor::new(content))
+            .csr_graph::<(), false>()
+            .unwrap();
+        assert_eq!(graph.edge_count(), 3);
+        assert_eq!(graph.adjacent_node_ids(id(0)).count(), 3);
+        assert_eq!(graph.adjacent_node_ids(id(1)).count(), 2);
+    }
+
+    #[test]
+    fn stream_progress() {
+        let mut reports = Vec::new();
+        let graph = EdgeListReader::new(Cursor::new("4\n0 1\n1 2\n2 0\n"))
+            .progress(2, |progress| reports.push(progress))
+            .adjacency_list::<(), false>()
+            .unwrap();
+
+        assert_eq!(bfs_scc(&graph).len(), 2);
+        assert_eq!(
+            reports,
+            vec![
+                Progress {
+                    node_count: 4,
+                    edges: 2,
+                    bytes: 10,
+                },
+                Progress {
+                    node_count: 4,
+                    edges: 3,
+                    bytes: 14,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn stream_errors() {
+        let result =
+            EdgeListReader::new(Cursor::new("2\n0 1 1.5\n1 2 x\n")).adjacency_list::<f64, true>();
+        assert!(matches!(
+            result,
+            Err(GraphError::Parse {
+                line: 3,
+                column: 3,
+                kind: ParseErrorKind::NodeOutOfRange { node_count: 2 },
+                ..
+            })
+        ));
+
+        let result = EdgeListReader::new(Cursor::new("2\n0 1 x\n")).csr_graph::<f64, true>();
+        assert!(matches!(
+            result,
+            Err(GraphError::Parse {
+                line: 2,
+                column: 5,
+                kind: ParseErrorKind::Float(_),
+                ..
+            })
+        ));
+    }
+
+    #[bench]
+    fn stream_graph_gross(b: &mut Bencher) {
+        b.iter(|| {
+            let graph = EdgeListReader::open("data/Graph_gross.txt")
+                .unwrap()
+                .adjacency_list::<(), false>()
+                .unwrap();
+            assert_eq!(bfs_scc(&graph).len(), 222);
+        })
+    }
+}
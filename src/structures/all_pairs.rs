@@ -0,0 +1,52 @@
+use super::Route;
+use crate::{graph::Base, prelude::NodeId};
+
+/// Distances between all pairs of nodes, indexed by the start and the end node.
+/// `next` holds the node following the start on a shortest path to the end.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(
+        serialize = "C: serde::Serialize, G::Id: serde::Serialize",
+        deserialize = "C: serde::Deserialize<'de>, G::Id: serde::Deserialize<'de>"
+    ))
+)]
+pub struct AllPairs<C, G: Base> {
+    pub distances: Vec<Vec<Option<C>>>,
+    pub next: Vec<Vec<Option<NodeId<G::Id>>>>,
+}
+
+impl<C: Clone, G: Base> AllPairs<C, G> {
+    pub fn with_count(count: usize) -> Self {
+        Self {
+            distances: vec![vec![None; count]; count],
+            next: vec![vec![None; count]; count],
+        }
+    }
+
+    pub fn count(&self) -> usize {
+        self.distances.len()
+    }
+
+    pub fn distance(&self, from: NodeId<G::Id>, to: NodeId<G::Id>) -> Option<&C> {
+        self.distances[from.as_usize()][to.as_usize()].as_ref()
+    }
+
+    pub fn next_hop(&self, from: NodeId<G::Id>, to: NodeId<G::Id>) -> Option<NodeId<G::Id>> {
+        self.next[from.as_usize()][to.as_usize()]
+    }
+
+    /// The nodes of a shortest path including both ends, `None` if `to` is not reachable
+    pub fn path(&self, from: NodeId<G::Id>, to: NodeId<G::Id>) -> Option<Route<G>> {
+        let mut path = vec![from];
+        let mut node = from;
+
+        while node != to {
+            node = self.next_hop(node, to)?;
+            path.push(node);
+        }
+
+        Some(Route::new(path))
+    }
+}
@@ -1,13 +1,27 @@
-use super::Parents;
+use super::{Parents, Route};
 use crate::{
-    graph::{Base, EdgeCost},
-    prelude::NodeId,
+    graph::{Base, Count, EdgeCost, Index},
+    prelude::{NodeId, Tree},
 };
 
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub struct Distances<C, G: Base<Weight: EdgeCost<Cost = C>>> {
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(
+        serialize = "C: serde::Serialize, G::Id: serde::Serialize",
+        deserialize = "C: serde::Deserialize<'de>, G::Id: serde::Deserialize<'de>"
+    ))
+)]
+pub struct Distances<C, G>
+where
+    G: Base,
+    G::Weight: EdgeCost<Cost = C>,
+{
     pub distances: Vec<Option<C>>,
     pub parents: Parents<G>,
+    // the reached node in each slot
+    nodes: Vec<Option<NodeId<G::Id>>>,
 }
 
 impl<C: Clone, G: Base<Weight: EdgeCost<Cost = C>>> Distances<C, G> {
@@ -15,19 +29,173 @@ impl<C: Clone, G: Base<Weight: EdgeCost<Cost = C>>> Distances<C, G> {
         Self {
             parents: Parents::with_count(count),
             distances: vec![None; count],
+            nodes: vec![None; count],
         }
     }
 
     pub fn insert(&mut self, from: NodeId<G::Id>, to: NodeId<G::Id>, cost: C) {
         self.parents.insert(from, to);
-        self.distances[to.as_usize()] = Some(cost);
+        self.add_cost(to, cost);
     }
 
     pub fn add_cost(&mut self, to: NodeId<G::Id>, cost: C) {
         self.distances[to.as_usize()] = Some(cost);
+        self.nodes[to.as_usize()] = Some(to);
     }
 
+    /// The distance of `node`, `None` if the slot belongs to another node
     pub fn distance(&self, node: NodeId<G::Id>) -> Option<&C> {
+        if self.nodes[node.as_usize()] != Some(node) {
+            return None;
+        }
         self.distances[node.as_usize()].as_ref()
     }
+
+    /// The distance of `node` or `unreachable` if it was not reached
+    pub fn distance_or(&self, node: NodeId<G::Id>, unreachable: C) -> C {
+        self.distance(node).cloned().unwrap_or(unreachable)
+    }
+
+    pub fn is_reachable(&self, node: NodeId<G::Id>) -> bool {
+        self.distance(node).is_some()
+    }
+
+    /// The reached nodes with their distance, the start included
+    pub fn reachable(&self) -> impl Iterator<Item = (NodeId<G::Id>, &C)> + '_ {
+        self.nodes
+            .iter()
+            .zip(&self.distances)
+            .filter_map(|(node, distance)| Some(((*node)?, distance.as_ref()?)))
+    }
+
+    /// The reached nodes still in `graph` with their distance, in the order of its nodes
+    pub fn reachable_in<'a>(
+        &'a self,
+        graph: &'a G,
+    ) -> impl Iterator<Item = (NodeId<G::Id>, &'a C)> + 'a
+    where
+        G: Index,
+    {
+        graph
+            .node_ids()
+            .filter_map(|node| self.distance(node).map(|distance| (node, distance)))
+    }
+
+    /// The start is the only reached node without a parent
+    pub fn start(&self) -> Option<NodeId<G::Id>> {
+        self.reachable()
+            .map(|(node, _)| node)
+            .find(|node| self.parents.parent(*node).is_none())
+    }
+
+    /// The shortest path from the start to `target`, `None` if `target` is not reachable
+    pub fn path_to(&self, target: NodeId<G::Id>) -> Option<Route<G>> {
+        if !self.is_reachable(target) {
+            return None;
+        }
+
+        let mut path = vec![target];
+        while let Some(parent) = self.parents.parent(*path.last().unwrap()) {
+            // a negative cycle can leave the parents without a start
+            if path.len() > self.parents.count() {
+                return None;
+            }
+
+            path.push(parent);
+        }
+
+        path.reverse();
+        Some(Route::new(path))
+    }
+
+    /// The shortest path tree rooted at the start
+    pub fn tree<'a>(&self, graph: &'a G) -> Option<Tree<'a, G>>
+    where
+        G: Count + Index,
+    {
+        Some(self.parents.tree(self.start()?, graph))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        algorithms::dijkstra,
+        graph::{Contains, Count, Create, Get, Index, Insert, Remove},
+        prelude::*,
+        test::{digraph, id},
+    };
+
+    #[test]
+    fn distances_path_to() {
+        let graph: AdjacencyList<_, _, true> = digraph("data/Wege1.txt").unwrap();
+        let distances = dijkstra(&graph, id(2), id(0)).unwrap();
+
+        let route = distances.path_to(id(0)).unwrap();
+        let cost = route
+            .edge_ids()
+            .map(|edge_id| graph.weight(edge_id).unwrap())
+            .sum::<f64>();
+
+        assert_eq!(route.first(), Some(id(2)));
+        assert_eq!(route.last(), Some(id(0)));
+        assert_eq!(cost, 6.0);
+        assert_eq!(distances.start(), Some(id(2)));
+        assert_eq!(distances.path_to(id(2)).unwrap().into_raw(), vec![id(2)]);
+    }
+
+    #[test]
+    fn distances_reachable() {
+        let mut graph = AdjacencyList::<usize, f64, true>::with_nodes(0..4);
+        graph.insert_edge(id(0), id(1), 2.0);
+        graph.insert_edge(id(1), id(2), 3.0);
+        graph.insert_edge(id(3), id(0), 1.0);
+
+        let distances = graph.bellman_ford(id(0)).unwrap();
+        let reachable = distances.reachable().collect::<Vec<_>>();
+
+        assert_eq!(reachable, vec![(id(0), &0.0), (id(1), &2.0), (id(2), &5.0)]);
+        assert!(distances.reachable_in(&graph).eq(reachable));
+        assert!(!distances.is_reachable(id(3)));
+        assert_eq!(distances.distance_or(id(3), f64::INFINITY), f64::INFINITY);
+        assert_eq!(distances.distance_or(id(2), f64::INFINITY), 5.0);
+        assert!(distances.path_to(id(3)).is_none());
+
+        let tree = distances.tree(&graph).unwrap();
+        assert_eq!(tree.root(), id(0));
+        assert_eq!(tree.node_ids().count(), 3);
+        assert_eq!(tree.edge_count(), 4);
+    }
+
+    #[test]
+    fn distances_stable_adj_list_reused_slot() {
+        let mut graph = StableAdjacencyList::<usize, f64, true>::new();
+        let a = graph.insert_node(0);
+        let b = graph.insert_node(1);
+        graph.remove_node(b);
+        // takes the slot of b with the next generation
+        let c = graph.insert_node(2);
+        graph.insert_edge(a, c, 1.0);
+
+        let distances = dijkstra(&graph, a, c).unwrap();
+        let reachable = distances.reachable().collect::<Vec<_>>();
+
+        assert_eq!(reachable, vec![(a, &0.0), (c, &1.0)]);
+        assert_eq!(distances.start(), Some(a));
+
+        let edge_id = graph.contains_edge(a, c).unwrap();
+        let tree = distances.tree(&graph).unwrap();
+        assert!(tree.edge_ids().any(|other| other == edge_id));
+        assert_eq!(graph.weight(edge_id), Some(&1.0));
+
+        // the slot of c is reused again, only the graph-aware lookup drops c
+        graph.remove_node(c);
+        let d = graph.insert_node(3);
+        assert!(!distances.is_reachable(d));
+        assert_eq!(distances.reachable().count(), 2);
+        assert_eq!(
+            distances.reachable_in(&graph).collect::<Vec<_>>(),
+            vec![(a, &0.0)]
+        );
+    }
 }
@@ -1,29 +1,53 @@
-/// A N*M sized sparse Matrix
+use std::convert::Infallible;
+
+/// A N*M sized sparse Matrix in compressed sparse row format.
+/// The columns of every row are sorted, so single elements are found by binary search.
+/// A matrix built with [SparseMatrixBuilder::build_all] keeps repeated elements next to each other.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "RawSparseMatrix<T>"))]
 pub struct SparseMatrix<T> {
+    offsets: Vec<usize>,
+    col_indices: Vec<usize>,
     values: Vec<T>,
-    row_indices: Vec<usize>,
+    col_count: usize,
+}
+
+/// The deserialized fields of a [SparseMatrix], checked before they are used
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct RawSparseMatrix<T> {
+    offsets: Vec<usize>,
     col_indices: Vec<usize>,
-    row_count: usize,
+    values: Vec<T>,
     col_count: usize,
 }
 
+#[cfg(feature = "serde")]
+impl<T> TryFrom<RawSparseMatrix<T>> for SparseMatrix<T> {
+    type Error = &'static str;
+
+    fn try_from(raw: RawSparseMatrix<T>) -> Result<Self, Self::Error> {
+        Self::from_raw_parts(raw.offsets, raw.col_indices, raw.values, raw.col_count)
+            .ok_or("the offsets or columns of the matrix are out of place")
+    }
+}
+
 const MODIFIER: usize = 4;
 
 impl<T> SparseMatrix<T> {
     pub fn with_capacity(row_count: usize, col_count: usize) -> Self {
         Self {
+            offsets: vec![0; row_count + 1],
+            col_indices: Vec::with_capacity(MODIFIER * (row_count + col_count)),
             values: Vec::with_capacity(MODIFIER * (row_count + col_count)),
-            row_indices: Vec::with_capacity(MODIFIER * row_count),
-            col_indices: Vec::with_capacity(MODIFIER * col_count),
-            row_count,
             col_count,
         }
     }
 
     /// Returns the number of rows in the matrix
     pub fn row_count(&self) -> usize {
-        self.row_count
+        self.offsets.len() - 1
     }
 
     /// Returns the number of columns in the matrix
@@ -44,13 +68,72 @@ impl<T> SparseMatrix<T> {
     /// Clears the matrix, removing all elements
     pub fn clear(&mut self) {
         self.values.clear();
-        self.row_indices.clear();
         self.col_indices.clear();
+        self.offsets.fill(0);
+    }
+
+    /// Returns the row of every element in storage order
+    fn row_indices(offsets: &[usize]) -> impl Iterator<Item = usize> + '_ {
+        offsets
+            .windows(2)
+            .enumerate()
+            .flat_map(|(row, range)| std::iter::repeat_n(row, range[1] - range[0]))
+    }
+
+    fn range(&self, row: usize) -> std::ops::Range<usize> {
+        self.offsets[row]..self.offsets[row + 1]
+    }
+
+    /// The row offsets, column indices and values of the compressed storage
+    pub(crate) fn raw_parts(&self) -> (&[usize], &[usize], &[T]) {
+        (&self.offsets, &self.col_indices, &self.values)
+    }
+
+    /// Rebuilds a matrix from its compressed storage,
+    /// `None` if the offsets or the sorted columns of a row are out of place.
+    /// Repeated columns are accepted, as [SparseMatrixBuilder::build_all] stores them.
+    pub(crate) fn from_raw_parts(
+        offsets: Vec<usize>,
+        col_indices: Vec<usize>,
+        values: Vec<T>,
+        col_count: usize,
+    ) -> Option<Self> {
+        let valid = offsets.first() == Some(&0)
+            && offsets.last() == Some(&col_indices.len())
+            && col_indices.len() == values.len()
+            && offsets.windows(2).all(|range| {
+                range[0] <= range[1]
+                    && range[1] <= col_indices.len()
+                    && col_indices[range[0]..range[1]]
+                        .windows(2)
+                        .all(|cols| cols[0] <= cols[1])
+                    && col_indices[range[0]..range[1]]
+                        .last()
+                        .is_none_or(|&col| col < col_count)
+            });
+
+        valid.then_some(Self {
+            offsets,
+            col_indices,
+            values,
+            col_count,
+        })
+    }
+
+    /// Returns the storage position of the element or where it would have to be inserted
+    fn search(&self, row: usize, col: usize) -> Result<usize, usize> {
+        let range = self.range(row);
+        let start = range.start;
+        match self.col_indices[range].binary_search(&col) {
+            Ok(i) => Ok(start + i),
+            Err(i) => Err(start + i),
+        }
     }
 
     pub fn into_iter(self) -> impl Iterator<Item = (usize, usize, T)> {
-        self.row_indices
-            .into_iter()
+        let rows = Self::row_indices(&self.offsets).collect::<Vec<_>>();
+
+        rows.into_iter()
             .zip(self.col_indices)
             .zip(self.values)
             .map(|((row, col), value)| (row, col, value))
@@ -58,97 +141,132 @@ impl<T> SparseMatrix<T> {
 
     /// Returns an iterator over the non-zero elements in the matrix
     pub fn iter(&self) -> impl Iterator<Item = (usize, usize, &T)> {
-        self.row_indices
-            .iter()
+        Self::row_indices(&self.offsets)
             .zip(&self.col_indices)
             .zip(&self.values)
-            .map(|((&row, &col), value)| (row, col, value))
+            .map(|((row, &col), value)| (row, col, value))
     }
 
     /// Returns an mutable iterator over the non-zero elements in the matrix
     pub fn iter_mut(&mut self) -> impl Iterator<Item = (usize, usize, &mut T)> {
-        self.row_indices
-            .iter()
+        Self::row_indices(&self.offsets)
             .zip(&self.col_indices)
             .zip(&mut self.values)
-            .map(|((&row, &col), value)| (row, col, value))
+            .map(|((row, &col), value)| (row, col, value))
     }
 
-    pub fn insert(&mut self, row: usize, col: usize, value: T) {
-        assert!(row <= self.row_count);
-        assert!(col <= self.col_count);
-
-        self.values.push(value);
-        self.row_indices.push(row);
-        self.col_indices.push(col);
+    /// Inserts the element and returns the element it replaced
+    pub fn insert(&mut self, row: usize, col: usize, value: T) -> Option<T> {
+        assert!(row < self.row_count());
+        assert!(col < self.col_count);
+
+        match self.search(row, col) {
+            Ok(index) => Some(std::mem::replace(&mut self.values[index], value)),
+            Err(index) => {
+                self.col_indices.insert(index, col);
+                self.values.insert(index, value);
+                for offset in &mut self.offsets[row + 1..] {
+                    *offset += 1;
+                }
+                None
+            }
+        }
     }
 
     pub fn get(&self, row: usize, col: usize) -> Option<&T> {
-        for i in 0..self.row_indices.len() {
-            if self.row_indices[i] == row && self.col_indices[i] == col {
-                return Some(&self.values[i]);
-            }
+        if row >= self.row_count() {
+            return None;
         }
-        None
+
+        let index = self.search(row, col).ok()?;
+        Some(&self.values[index])
     }
 
     pub fn get_mut(&mut self, row: usize, col: usize) -> Option<&mut T> {
-        for i in 0..self.row_indices.len() {
-            if self.row_indices[i] == row && self.col_indices[i] == col {
-                return Some(&mut self.values[i]);
+        if row >= self.row_count() {
+            return None;
+        }
+
+        let index = self.search(row, col).ok()?;
+        Some(&mut self.values[index])
+    }
+
+    /// Removes the element at the given position and returns it
+    pub fn remove(&mut self, row: usize, col: usize) -> Option<T> {
+        if row >= self.row_count() {
+            return None;
+        }
+
+        let index = self.search(row, col).ok()?;
+        self.col_indices.remove(index);
+        for offset in &mut self.offsets[row + 1..] {
+            *offset -= 1;
+        }
+        Some(self.values.remove(index))
+    }
+
+    /// Removes a row with all its elements, the following rows are shifted up by one
+    pub fn remove_row(&mut self, row: usize) {
+        let range = self.range(row);
+        let len = range.len();
+
+        self.col_indices.drain(range.clone());
+        self.values.drain(range);
+        self.offsets.remove(row + 1);
+        for offset in &mut self.offsets[row + 1..] {
+            *offset -= len;
+        }
+    }
+
+    /// Removes a column with all its elements, the following columns are shifted left by one
+    pub fn remove_col(&mut self, col: usize) {
+        let col_indices = std::mem::take(&mut self.col_indices);
+        let values = std::mem::take(&mut self.values);
+        let rows = Self::row_indices(&self.offsets).collect::<Vec<_>>();
+
+        self.offsets.fill(0);
+        for ((row, c), value) in rows.into_iter().zip(col_indices).zip(values) {
+            if c != col {
+                self.col_indices.push(if c > col { c - 1 } else { c });
+                self.values.push(value);
+                self.offsets[row + 1] += 1;
             }
         }
-        None
+        for row in 0..self.row_count() {
+            self.offsets[row + 1] += self.offsets[row];
+        }
+        self.col_count = self.col_count.saturating_sub(1);
+    }
+
+    /// Reserves capacity for at least additional more elements
+    pub fn reserve(&mut self, additional: usize) {
+        self.values.reserve(additional);
+        self.col_indices.reserve(additional);
     }
 
     /// Returns the elements of a specific row
     pub fn row(&self, row: usize) -> impl Iterator<Item = (usize, &T)> {
-        (0..self.row_indices.len()).filter_map(move |i| {
-            if self.row_indices[i] == row {
-                Some((self.col_indices[i], &self.values[i]))
-            } else {
-                None
-            }
-        })
+        let range = self.range(row);
+        self.col_indices[range.clone()]
+            .iter()
+            .copied()
+            .zip(&self.values[range])
     }
 
     /// Returns the elements of a specific row
     pub fn row_mut(&mut self, row: usize) -> impl Iterator<Item = (usize, &mut T)> {
-        let (ids, col_ids): (Vec<_>, Vec<_>) = (0..self.row_indices.len())
-            .filter_map(|i| {
-                if &self.row_indices[i] == &row {
-                    Some((i, *&self.col_indices[i]))
-                } else {
-                    None
-                }
-            })
-            .unzip();
-
-        let values = self
-            .values
-            .iter_mut()
-            .enumerate()
-            .filter_map(
-                move |(i, val)| {
-                    if ids.contains(&i) {
-                        Some(val)
-                    } else {
-                        None
-                    }
-                },
-            );
-
-        col_ids.into_iter().zip(values)
+        let range = self.range(row);
+        self.col_indices[range.clone()]
+            .iter()
+            .copied()
+            .zip(&mut self.values[range])
     }
 
     /// Returns the elements of a specific column
     pub fn col(&self, col: usize) -> impl Iterator<Item = (usize, &T)> {
-        (0..self.col_indices.len()).filter_map(move |i| {
-            if self.col_indices[i] == col {
-                Some((self.row_indices[i], &self.values[i]))
-            } else {
-                None
-            }
+        (0..self.row_count()).filter_map(move |row| {
+            let index = self.search(row, col).ok()?;
+            Some((row, &self.values[index]))
         })
     }
 }
@@ -156,14 +274,122 @@ impl<T> SparseMatrix<T> {
 impl<T: Clone> SparseMatrix<T> {
     /// Transposes the matrix, swapping the rows and columns
     pub fn transpose(&self) -> Self {
-        let mut transposed = Self::with_capacity(self.col_count, self.row_count);
-        for i in 0..self.values.len() {
-            let row = self.col_indices[i];
-            let col = self.row_indices[i];
-            let value = self.values[i].clone();
-            transposed.insert(row, col, value);
+        let mut offsets = vec![0; self.col_count + 1];
+        for &col in &self.col_indices {
+            offsets[col + 1] += 1;
+        }
+        for col in 0..self.col_count {
+            offsets[col + 1] += offsets[col];
+        }
+
+        // rows are visited in order, so the columns of the transposed rows stay sorted
+        let mut next = offsets.clone();
+        let mut col_indices = vec![0; self.nnz()];
+        let mut values = vec![None; self.nnz()];
+        for (row, col, value) in self.iter() {
+            let index = next[col];
+            next[col] += 1;
+            col_indices[index] = row;
+            values[index] = Some(value.clone());
+        }
+
+        Self {
+            offsets,
+            col_indices,
+            values: values.into_iter().flatten().collect(),
+            col_count: self.row_count(),
         }
-        transposed
+    }
+}
+
+/// Collects elements in any order and builds the [SparseMatrix] at once.
+pub struct SparseMatrixBuilder<T> {
+    row_count: usize,
+    col_count: usize,
+    elements: Vec<(usize, usize, T)>,
+}
+
+impl<T> SparseMatrixBuilder<T> {
+    pub fn with_capacity(row_count: usize, col_count: usize) -> Self {
+        Self {
+            row_count,
+            col_count,
+            elements: Vec::with_capacity(MODIFIER * (row_count + col_count)),
+        }
+    }
+
+    pub fn insert(&mut self, row: usize, col: usize, value: T) -> &mut Self {
+        assert!(row < self.row_count);
+        assert!(col < self.col_count);
+
+        self.elements.push((row, col, value));
+        self
+    }
+
+    /// Builds the matrix, duplicates are merged into the first inserted element by `merge`.
+    pub fn build(self, mut merge: impl FnMut(&mut T, T)) -> SparseMatrix<T> {
+        self.try_build(|_, _, value, other| {
+            merge(value, other);
+            Ok::<_, Infallible>(None)
+        })
+        .unwrap_or_else(|never| match never {})
+    }
+
+    /// Builds the matrix, duplicates are kept as elements of their own in insertion order.
+    pub fn build_all(self) -> SparseMatrix<T> {
+        self.try_build(|_, _, _, other| Ok::<_, Infallible>(Some(other)))
+            .unwrap_or_else(|never| match never {})
+    }
+
+    /// Builds the matrix, `Err` with the row and column of a duplicate if there is one.
+    pub fn build_unique(self) -> Result<SparseMatrix<T>, (usize, usize)> {
+        self.try_build(|row, col, _, _| Err((row, col)))
+    }
+
+    /// `merge` either merges a duplicate into the previous element
+    /// or returns it to be kept as an element of its own.
+    fn try_build<E>(
+        self,
+        mut merge: impl FnMut(usize, usize, &mut T, T) -> Result<Option<T>, E>,
+    ) -> Result<SparseMatrix<T>, E> {
+        let Self {
+            row_count,
+            col_count,
+            mut elements,
+        } = self;
+
+        // the sort is stable, so duplicates are merged in insertion order
+        elements.sort_by_key(|(row, col, _)| (*row, *col));
+
+        let mut matrix = SparseMatrix {
+            offsets: vec![0; row_count + 1],
+            col_indices: Vec::with_capacity(elements.len()),
+            values: Vec::with_capacity(elements.len()),
+            col_count,
+        };
+        let mut last = None;
+
+        for (row, col, value) in elements {
+            let value = if last == Some((row, col)) {
+                match merge(row, col, matrix.values.last_mut().unwrap(), value)? {
+                    Some(value) => value,
+                    None => continue,
+                }
+            } else {
+                value
+            };
+
+            matrix.offsets[row + 1] += 1;
+            matrix.col_indices.push(col);
+            matrix.values.push(value);
+            last = Some((row, col));
+        }
+
+        for row in 0..row_count {
+            matrix.offsets[row + 1] += matrix.offsets[row];
+        }
+
+        Ok(matrix)
     }
 }
 
@@ -230,4 +456,109 @@ mod test {
         assert_eq!(transposed.get(2, 1), None);
         assert_eq!(transposed.get(2, 2), Some(&5));
     }
+
+    #[test]
+    fn sparse_matrix_insert_replaces() {
+        let mut matrix = SparseMatrix::with_capacity(3, 3);
+
+        assert_eq!(matrix.insert(1, 2, 1), None);
+        assert_eq!(matrix.insert(1, 0, 2), None);
+        assert_eq!(matrix.insert(1, 2, 3), Some(1));
+
+        assert_eq!(matrix.nnz(), 2);
+        assert_eq!(matrix.row(1).collect::<Vec<_>>(), vec![(0, &2), (2, &3)]);
+        assert_eq!(matrix.remove(1, 0), Some(2));
+        assert_eq!(matrix.remove(1, 0), None);
+        assert_eq!(matrix.iter().collect::<Vec<_>>(), vec![(1, 2, &3)]);
+    }
+
+    #[test]
+    fn sparse_matrix_remove_row_and_col() {
+        let mut matrix = SparseMatrix::with_capacity(3, 3);
+
+        matrix.insert(0, 0, 1);
+        matrix.insert(0, 2, 2);
+        matrix.insert(1, 1, 3);
+        matrix.insert(2, 0, 4);
+        matrix.insert(2, 2, 5);
+
+        matrix.remove_row(1);
+        matrix.remove_col(0);
+
+        assert_eq!(matrix.row_count(), 2);
+        assert_eq!(matrix.col_count(), 2);
+        assert_eq!(
+            matrix.iter().collect::<Vec<_>>(),
+            vec![(0, 1, &2), (1, 1, &5)]
+        );
+    }
+
+    #[test]
+    fn sparse_matrix_builder_merges_duplicates() {
+        let mut builder = SparseMatrixBuilder::with_capacity(3, 3);
+
+        builder
+            .insert(2, 1, 1)
+            .insert(0, 2, 2)
+            .insert(2, 1, 3)
+            .insert(0, 0, 4);
+        let matrix = builder.build(|value, other| *value += other);
+
+        assert_eq!(matrix.nnz(), 3);
+        assert_eq!(
+            matrix.into_iter().collect::<Vec<_>>(),
+            vec![(0, 0, 4), (0, 2, 2), (2, 1, 4)]
+        );
+    }
+
+    #[test]
+    fn sparse_matrix_builder_unique() {
+        let mut builder = SparseMatrixBuilder::with_capacity(3, 3);
+        builder.insert(2, 1, 1).insert(0, 2, 2);
+        assert_eq!(builder.build_unique().unwrap().nnz(), 2);
+
+        let mut builder = SparseMatrixBuilder::with_capacity(3, 3);
+        builder.insert(2, 1, 1).insert(0, 2, 2).insert(2, 1, 3);
+        assert_eq!(builder.build_unique(), Err((2, 1)));
+    }
+
+    #[test]
+    fn sparse_matrix_builder_keeps_duplicates() {
+        let mut builder = SparseMatrixBuilder::with_capacity(3, 3);
+        builder.insert(2, 1, 1).insert(0, 2, 2).insert(2, 1, 3);
+        let matrix = builder.build_all();
+
+        assert_eq!(
+            matrix.iter().collect::<Vec<_>>(),
+            vec![(0, 2, &2), (2, 1, &1), (2, 1, &3)]
+        );
+
+        let (offsets, col_indices, values) = matrix.raw_parts();
+        let rebuilt = SparseMatrix::from_raw_parts(
+            offsets.to_vec(),
+            col_indices.to_vec(),
+            values.to_vec(),
+            matrix.col_count(),
+        );
+        assert_eq!(rebuilt, Some(matrix));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn sparse_matrix_serde_rejects_invalid_parts() {
+        let mut builder = SparseMatrixBuilder::with_capacity(3, 3);
+        builder.insert(2, 1, 1).insert(0, 2, 2);
+        let matrix = builder.build_all();
+
+        let json = serde_json::to_string(&matrix).unwrap();
+        let parsed: SparseMatrix<i32> = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, matrix);
+
+        // the column of the second element is out of range
+        let json = r#"{"offsets":[0,1,1,2],"col_indices":[2,3],"values":[2,1],"col_count":3}"#;
+        assert!(serde_json::from_str::<SparseMatrix<i32>>(json).is_err());
+
+        let json = r#"{"offsets":[0,1,1,3],"col_indices":[2,1],"values":[2,1],"col_count":3}"#;
+        assert!(serde_json::from_str::<SparseMatrix<i32>>(json).is_err());
+    }
 }
@@ -1,8 +1,10 @@
+pub use all_pairs::*;
 pub use distances::*;
 pub use matrix::*;
 pub use parents::*;
 pub use route::*;
 
+mod all_pairs;
 mod distances;
 mod matrix;
 mod parents;
@@ -1,14 +1,23 @@
 use super::Route;
 use crate::{
-    graph::Base,
-    prelude::{EdgeId, NodeId},
+    graph::{Base, Count, Index},
+    prelude::{EdgeId, NodeId, Tree},
 };
 
+/// The edge from the parent of each node, in the slot of the node
 #[derive(Debug, PartialEq, Eq, Clone)]
-pub struct Parents<G: Base>(Vec<Option<NodeId<G::Id>>>);
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(
+        serialize = "G::Id: serde::Serialize",
+        deserialize = "G::Id: serde::Deserialize<'de>"
+    ))
+)]
+pub struct Parents<G: Base>(Vec<Option<EdgeId<G::Id>>>);
 
 impl<G: Base> Parents<G> {
-    pub fn new(parents: Vec<Option<NodeId<G::Id>>>) -> Self {
+    pub fn new(parents: Vec<Option<EdgeId<G::Id>>>) -> Self {
         Self(parents)
     }
 
@@ -16,7 +25,7 @@ impl<G: Base> Parents<G> {
         Self(vec![None; count])
     }
 
-    pub fn with_parents(parents: Vec<Option<NodeId<G::Id>>>) -> Self {
+    pub fn with_parents(parents: Vec<Option<EdgeId<G::Id>>>) -> Self {
         Self(parents)
     }
 
@@ -29,31 +38,74 @@ impl<G: Base> Parents<G> {
     }
 
     pub fn insert(&mut self, from: NodeId<G::Id>, to: NodeId<G::Id>) -> Option<NodeId<G::Id>> {
-        std::mem::replace(&mut self.0[to.as_usize()], Some(from))
+        let edge_id = EdgeId::new_unchecked(from, to);
+        self.0[to.as_usize()]
+            .replace(edge_id)
+            .filter(|previous| previous.to() == to)
+            .map(|previous| previous.from())
     }
 
+    /// The parent of `child`, `None` if the slot belongs to another node
     pub fn parent(&self, child: NodeId<G::Id>) -> Option<NodeId<G::Id>> {
-        if let Some(Some(parent)) = self.0.get(child.as_usize()) {
-            Some(*parent)
-        } else {
-            None
+        match self.0.get(child.as_usize()) {
+            Some(Some(edge_id)) if edge_id.to() == child => Some(edge_id.from()),
+            _ => None,
         }
     }
 
     pub unsafe fn parent_unchecked(&self, child: NodeId<G::Id>) -> NodeId<G::Id> {
         // self.0.get_unchecked(child.as_usize()).unwrap_unchecked()
-        self.0[child.as_usize()].unwrap()
+        self.0[child.as_usize()].unwrap().from()
+    }
+
+    /// The parent in the slot at `index`, for callers without the id of the node
+    pub(crate) fn parent_at(&self, index: usize) -> Option<NodeId<G::Id>> {
+        self.0.get(index)?.map(|edge_id| edge_id.from())
     }
 
     pub fn edge_ids(&self) -> impl Iterator<Item = EdgeId<G::Id>> + '_ {
-        self.0.iter().enumerate().filter_map(|(to, parent)| {
-            if let Some(from) = parent {
-                let to = NodeId::new_unchecked(to.into());
-                Some(EdgeId::new_unchecked(*from, to))
-            } else {
-                None
+        self.0.iter().flatten().copied()
+    }
+
+    /// The edges from the parents to the nodes still in `graph`, in the order of its nodes
+    pub fn edge_ids_in<'a>(&'a self, graph: &'a G) -> impl Iterator<Item = EdgeId<G::Id>> + 'a
+    where
+        G: Index,
+    {
+        graph
+            .node_ids()
+            .filter_map(|to| self.parent(to).map(|from| EdgeId::new_unchecked(from, to)))
+    }
+
+    /// The nodes from `from` to `to`, `None` if `from` is no ancestor of `to`
+    pub fn path(&self, from: NodeId<G::Id>, to: NodeId<G::Id>) -> Option<Route<G>> {
+        let mut path = vec![to];
+        let mut node = to;
+
+        while node != from {
+            // every node is visited at most once unless the parents contain a cycle
+            if path.len() > self.count() {
+                return None;
             }
-        })
+
+            node = self.parent(node)?;
+            path.push(node);
+        }
+
+        path.reverse();
+        Some(Route::new(path))
+    }
+
+    /// The tree of all parent edges between the nodes of `graph`
+    pub fn tree<'a>(&self, root: NodeId<G::Id>, graph: &'a G) -> Tree<'a, G>
+    where
+        G: Count + Index,
+    {
+        let mut tree = Tree::new(root, graph);
+        for edge_id in self.edge_ids_in(graph) {
+            tree.insert(edge_id.from(), edge_id.to());
+        }
+        tree
     }
 
     // only use this if parents is known to have cycle and "node" is in it
@@ -104,7 +156,35 @@ mod tests {
 
     // Helper function to create a Parents struct from a given list of parent nodes
     fn create_parents(parents: Vec<Option<usize>>) -> Parents<PhantomGraph> {
-        Parents(parents.into_iter().map(|opt| opt.map(id)).collect())
+        Parents(
+            parents
+                .into_iter()
+                .enumerate()
+                .map(|(child, parent)| {
+                    parent.map(|parent| EdgeId::new_unchecked(id(parent), id(child)))
+                })
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn parents_path() {
+        let parents = create_parents(vec![None, Some(0), Some(1), Some(1), None]);
+
+        assert_eq!(
+            parents.path(id(0), id(2)).unwrap().into_raw(),
+            vec![id(0), id(1), id(2)]
+        );
+        assert_eq!(
+            parents.path(id(1), id(3)).unwrap().into_raw(),
+            vec![id(1), id(3)]
+        );
+        assert_eq!(parents.path(id(4), id(4)).unwrap().into_raw(), vec![id(4)]);
+        assert!(parents.path(id(2), id(3)).is_none());
+        assert!(parents.path(id(0), id(4)).is_none());
+
+        let cycle = create_parents(vec![Some(1), Some(0), None]);
+        assert!(cycle.path(id(2), id(0)).is_none());
     }
 
     #[test]
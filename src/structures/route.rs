@@ -4,6 +4,14 @@ use crate::{
 };
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(
+        serialize = "G::Id: serde::Serialize",
+        deserialize = "G::Id: serde::Deserialize<'de>"
+    ))
+)]
 pub struct Route<G: Base>(Vec<NodeId<G::Id>>);
 
 impl<G: Base> From<Vec<NodeId<G::Id>>> for Route<G> {
@@ -0,0 +1,539 @@
+use crate::{
+    edge_list::{EdgeList, Tokens},
+    error::{GraphError, GraphResult, ParseErrorKind},
+    graph::Base,
+    prelude::NodeId,
+    structures::{Route, SparseMatrixBuilder},
+};
+use std::{
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum WeightType {
+    Euc2d,
+    Ceil2d,
+    Geo,
+    Att,
+    Explicit,
+}
+
+/// Order of the values in an explicit edge weight section.
+/// The column formats of a symmetric matrix are the row formats of the other triangle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum WeightFormat {
+    FullMatrix,
+    UpperRow,
+    LowerRow,
+    UpperDiagRow,
+    LowerDiagRow,
+}
+
+impl WeightFormat {
+    /// Returns the positions of the values in the order they are listed
+    fn positions(self, n: usize) -> Box<dyn Iterator<Item = (usize, usize)>> {
+        match self {
+            Self::FullMatrix => Box::new((0..n).flat_map(move |i| (0..n).map(move |j| (i, j)))),
+            Self::UpperRow => Box::new((0..n).flat_map(move |i| (i + 1..n).map(move |j| (i, j)))),
+            Self::LowerRow => Box::new((0..n).flat_map(|i| (0..i).map(move |j| (i, j)))),
+            Self::UpperDiagRow => Box::new((0..n).flat_map(move |i| (i..n).map(move |j| (i, j)))),
+            Self::LowerDiagRow => Box::new((0..n).flat_map(|i| (0..=i).map(move |j| (i, j)))),
+        }
+    }
+}
+
+/// Rounds to the nearest integer like `nint` of the TSPLIB documentation
+fn nint(x: f64) -> f64 {
+    (x + 0.5).floor()
+}
+
+/// Converts a `DDD.MM` coordinate of a GEO instance to radians
+fn geo_radians(x: f64) -> f64 {
+    // the reference implementation truncates pi, the published optima depend on it
+    #[allow(clippy::approx_constant)]
+    const PI: f64 = 3.141592;
+
+    let degrees = x.trunc();
+    let minutes = x - degrees;
+    PI * (degrees + 5.0 * minutes / 3.0) / 180.0
+}
+
+impl WeightType {
+    fn distance(self, (xi, yi): (f64, f64), (xj, yj): (f64, f64)) -> f64 {
+        let (dx, dy) = (xi - xj, yi - yj);
+
+        match self {
+            Self::Euc2d => nint((dx * dx + dy * dy).sqrt()),
+            Self::Ceil2d => (dx * dx + dy * dy).sqrt().ceil(),
+            Self::Att => {
+                let r = ((dx * dx + dy * dy) / 10.0).sqrt();
+                let t = nint(r);
+                if t < r {
+                    t + 1.0
+                } else {
+                    t
+                }
+            }
+            Self::Geo => {
+                const RRR: f64 = 6378.388;
+
+                let (lat_i, lon_i) = (geo_radians(xi), geo_radians(yi));
+                let (lat_j, lon_j) = (geo_radians(xj), geo_radians(yj));
+                let q1 = (lon_i - lon_j).cos();
+                let q2 = (lat_i - lat_j).cos();
+                let q3 = (lat_i + lat_j).cos();
+                (RRR * (0.5 * ((1.0 + q1) * q2 - (1.0 - q1) * q3)).acos() + 1.0).trunc()
+            }
+            Self::Explicit => unreachable!("explicit weights have no coordinates"),
+        }
+    }
+}
+
+/// A symmetric TSP instance in the TSPLIB format, loaded as a complete graph.
+/// The node ids of the file start at one, here they are shifted to start at zero.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tsplib {
+    pub name: String,
+    pub edge_list: EdgeList<usize, f64>,
+}
+
+/// Parses a node id starting at one
+fn parse_node(tokens: &mut Tokens, dimension: usize) -> GraphResult<usize> {
+    let token = tokens.token()?;
+    let node = tokens.value::<usize>(token)?;
+
+    if node == 0 || node > dimension {
+        let kind = ParseErrorKind::NodeOutOfRange {
+            node_count: dimension,
+        };
+        return Err(tokens.error(Some(token), kind));
+    }
+
+    Ok(node - 1)
+}
+
+/// Returns the dimension or an error pointing at the section that needs it
+fn require_dimension(
+    tokens: &Tokens,
+    section: &str,
+    dimension: Option<usize>,
+) -> GraphResult<usize> {
+    let kind = ParseErrorKind::Expected("a DIMENSION first");
+    dimension.ok_or_else(|| tokens.error(Some(section), kind))
+}
+
+impl FromStr for Tsplib {
+    type Err = GraphError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = Tokens::lines(s).filter(|tokens| tokens.len() > 0);
+        let end = s.lines().count() + 1;
+
+        let mut name = String::new();
+        let mut dimension = None;
+        let mut weight_type = None;
+        let mut weight_format = None;
+        let mut coords = Vec::new();
+        let mut weights = Vec::new();
+
+        while let Some(mut tokens) = lines.next() {
+            if let Some((key, value)) = tokens.text.split_once(':') {
+                let value = value.trim();
+
+                match key.trim() {
+                    "NAME" => name = value.to_string(),
+                    "TYPE" if value != "TSP" => {
+                        let kind = ParseErrorKind::Expected("a symmetric TSP");
+                        return Err(tokens.error(Some(value), kind));
+                    }
+                    "DIMENSION" => dimension = Some(tokens.value::<usize>(value)?),
+                    "EDGE_WEIGHT_TYPE" => {
+                        weight_type = Some(match value {
+                            "EUC_2D" => WeightType::Euc2d,
+                            "CEIL_2D" => WeightType::Ceil2d,
+                            "GEO" => WeightType::Geo,
+                            "ATT" => WeightType::Att,
+                            "EXPLICIT" => WeightType::Explicit,
+                            _ => {
+                                let kind = ParseErrorKind::Expected(
+                                    "EUC_2D, CEIL_2D, GEO, ATT or EXPLICIT",
+                                );
+                                return Err(tokens.error(Some(value), kind));
+                            }
+                        })
+                    }
+                    "EDGE_WEIGHT_FORMAT" => {
+                        weight_format = match value {
+                            "FUNCTION" => None,
+                            "FULL_MATRIX" => Some(WeightFormat::FullMatrix),
+                            "UPPER_ROW" | "LOWER_COL" => Some(WeightFormat::UpperRow),
+                            "LOWER_ROW" | "UPPER_COL" => Some(WeightFormat::LowerRow),
+                            "UPPER_DIAG_ROW" | "LOWER_DIAG_COL" => Some(WeightFormat::UpperDiagRow),
+                            "LOWER_DIAG_ROW" | "UPPER_DIAG_COL" => Some(WeightFormat::LowerDiagRow),
+                            _ => {
+                                let kind = ParseErrorKind::Expected("a matrix or FUNCTION format");
+                                return Err(tokens.error(Some(value), kind));
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+
+                continue;
+            }
+
+            let section = tokens.token()?;
+            match section {
+                "NODE_COORD_SECTION" => {
+                    let dimension = require_dimension(&tokens, section, dimension)?;
+                    coords = vec![None; dimension];
+
+                    for _ in 0..dimension {
+                        let mut tokens = lines.next().unwrap_or_else(|| Tokens::new(end, ""));
+                        let node = parse_node(&mut tokens, dimension)?;
+                        let x = tokens.parse::<f64>()?;
+                        let y = tokens.parse::<f64>()?;
+                        coords[node] = Some((x, y));
+                    }
+                }
+                "EDGE_WEIGHT_SECTION" => {
+                    let dimension = require_dimension(&tokens, section, dimension)?;
+                    let format = weight_format.ok_or_else(|| {
+                        let kind = ParseErrorKind::Expected("an EDGE_WEIGHT_FORMAT first");
+                        tokens.error(Some(section), kind)
+                    })?;
+
+                    // the values are listed across lines regardless of the rows of the matrix
+                    let mut positions = format.positions(dimension).peekable();
+                    while positions.peek().is_some() {
+                        let Some(mut tokens) = lines.next() else {
+                            let end = Tokens::new(end, "");
+                            return Err(end.error(None, ParseErrorKind::MissingToken));
+                        };
+                        for _ in 0..tokens.len() {
+                            let Some((i, j)) = positions.next() else {
+                                break;
+                            };
+                            weights.push((i, j, tokens.parse::<f64>()?));
+                        }
+                    }
+                }
+                "DISPLAY_DATA_SECTION" => {
+                    let dimension = require_dimension(&tokens, section, dimension)?;
+                    lines.by_ref().take(dimension).for_each(drop);
+                }
+                "EOF" => break,
+                _ => {
+                    let kind = ParseErrorKind::Expected("a specification or a section");
+                    return Err(tokens.error(Some(section), kind));
+                }
+            }
+        }
+
+        let end = Tokens::new(end, "");
+        let dimension = dimension.ok_or_else(|| end.error(None, ParseErrorKind::MissingToken))?;
+        let weight_type = weight_type
+            .ok_or_else(|| end.error(None, ParseErrorKind::Expected("an EDGE_WEIGHT_TYPE")))?;
+
+        let mut edges = SparseMatrixBuilder::with_capacity(dimension, dimension);
+        match weight_type {
+            WeightType::Explicit => {
+                for (i, j, weight) in weights {
+                    if i != j {
+                        edges.insert(i.min(j), i.max(j), weight);
+                    }
+                }
+            }
+            _ => {
+                let coords = coords
+                    .into_iter()
+                    .collect::<Option<Vec<_>>>()
+                    .filter(|coords| coords.len() == dimension)
+                    .ok_or_else(|| {
+                        end.error(None, ParseErrorKind::Expected("coordinates for all nodes"))
+                    })?;
+
+                for i in 0..dimension {
+                    for j in i + 1..dimension {
+                        edges.insert(i, j, weight_type.distance(coords[i], coords[j]));
+                    }
+                }
+            }
+        }
+
+        Ok(Self {
+            name,
+            edge_list: EdgeList {
+                nodes: (0..dimension).collect(),
+                edges: edges.build(|weight, other| *weight = other),
+                node_count: dimension,
+            },
+        })
+    }
+}
+
+/// A tour in the TSPLIB format.
+/// The node ids of the file start at one, here they are shifted to start at zero.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tour {
+    pub name: String,
+    pub nodes: Vec<usize>,
+}
+
+impl FromStr for Tour {
+    type Err = GraphError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = Tokens::lines(s).filter(|tokens| tokens.len() > 0);
+
+        let mut name = String::new();
+        let mut dimension = None;
+        let mut nodes = Vec::new();
+
+        while let Some(mut tokens) = lines.next() {
+            if let Some((key, value)) = tokens.text.split_once(':') {
+                let value = value.trim();
+
+                match key.trim() {
+                    "NAME" => name = value.to_string(),
+                    "TYPE" if value != "TOUR" => {
+                        let kind = ParseErrorKind::Expected("a TOUR");
+                        return Err(tokens.error(Some(value), kind));
+                    }
+                    "DIMENSION" => dimension = Some(tokens.value::<usize>(value)?),
+                    _ => {}
+                }
+
+                continue;
+            }
+
+            let section = tokens.token()?;
+            match section {
+                "TOUR_SECTION" => {
+                    let dimension = require_dimension(&tokens, section, dimension)?;
+
+                    // the nodes are terminated by -1
+                    'section: for mut tokens in lines.by_ref() {
+                        for _ in 0..tokens.len() {
+                            let token = tokens.token()?;
+                            if token == "-1" {
+                                break 'section;
+                            }
+                            let node = tokens.value::<usize>(token)?;
+                            if node == 0 || node > dimension {
+                                let kind = ParseErrorKind::NodeOutOfRange {
+                                    node_count: dimension,
+                                };
+                                return Err(tokens.error(Some(token), kind));
+                            }
+                            nodes.push(node - 1);
+                        }
+                    }
+                }
+                "EOF" => break,
+                _ => {
+                    let kind = ParseErrorKind::Expected("a specification or the TOUR_SECTION");
+                    return Err(tokens.error(Some(section), kind));
+                }
+            }
+        }
+
+        Ok(Self { name, nodes })
+    }
+}
+
+impl Display for Tour {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if !self.name.is_empty() {
+            writeln!(f, "NAME : {}", self.name)?;
+        }
+
+        writeln!(f, "TYPE : TOUR")?;
+        writeln!(f, "DIMENSION : {}", self.nodes.len())?;
+        writeln!(f, "TOUR_SECTION")?;
+
+        for node in &self.nodes {
+            writeln!(f, "{}", node + 1)?;
+        }
+
+        writeln!(f, "-1")?;
+        writeln!(f, "EOF")
+    }
+}
+
+impl<G: Base<Id = usize>> From<Tour> for Route<G> {
+    fn from(tour: Tour) -> Self {
+        Route::new(tour.nodes.into_iter().map(NodeId::new_unchecked).collect())
+    }
+}
+
+impl<G: Base<Id = usize>> From<&Route<G>> for Tour {
+    fn from(route: &Route<G>) -> Self {
+        Self {
+            name: String::new(),
+            nodes: route
+                .node_ids()
+                .iter()
+                .map(|node_id| node_id.as_usize())
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    extern crate test;
+
+    use super::{Tour, Tsplib};
+    use crate::{
+        algorithms::nearest_neighbor_from_first,
+        error::{GraphError, ParseErrorKind},
+        graph::{Count, Get},
+        prelude::*,
+        structures::Route,
+        test::id,
+    };
+    use std::{fs, str::FromStr};
+    use test::Bencher;
+
+    fn tsplib(path: &str) -> AdjacencyList<usize, f64> {
+        let content = fs::read_to_string(path).unwrap();
+        let tsplib = Tsplib::from_str(&content).unwrap();
+        AdjacencyList::try_from(tsplib.edge_list).unwrap()
+    }
+
+    fn explicit(format: &str, weights: &str) -> Tsplib {
+        let content = format!(
+            "NAME : square\nTYPE : TSP\nDIMENSION : 4\nEDGE_WEIGHT_TYPE : EXPLICIT\n\
+             EDGE_WEIGHT_FORMAT : {format}\nEDGE_WEIGHT_SECTION\n{weights}\nEOF\n"
+        );
+        Tsplib::from_str(&content).unwrap()
+    }
+
+    #[test]
+    fn tsplib_burma_14_optimum() {
+        let graph = tsplib("data/burma14.tsp");
+
+        assert_eq!(graph.node_count(), 14);
+        assert_eq!(graph.edge_count(), 14 * 13 / 2);
+
+        let content = fs::read_to_string("data/burma14.opt.tour").unwrap();
+        let route: Route<AdjacencyList<usize, f64>> = Tour::from_str(&content).unwrap().into();
+        let total = route
+            .edge_id_cycle()
+            .map(|edge_id| graph.weight(edge_id).unwrap())
+            .sum::<f64>();
+
+        assert_eq!(total, 3323.0);
+    }
+
+    #[bench]
+    fn nearest_neighbor_burma_14_adj_list(b: &mut Bencher) {
+        let graph = tsplib("data/burma14.tsp");
+
+        b.iter(|| {
+            let total = nearest_neighbor_from_first(&graph).unwrap().1;
+            assert!(total >= 3323.0);
+        })
+    }
+
+    #[test]
+    fn tsplib_explicit_formats() {
+        let full = explicit("FULL_MATRIX", "0 1 2 3\n1 0 4 5\n2 4 0 6\n3 5 6 0");
+        let upper = explicit("UPPER_ROW", "1 2 3 4 5 6");
+        let lower_diag = explicit("LOWER_DIAG_ROW", "0\n1 0\n2 4 0\n3 5 6 0");
+        let upper_col = explicit("UPPER_COL", "1\n2 4\n3 5 6");
+
+        assert_eq!(full.edge_list, upper.edge_list);
+        assert_eq!(full.edge_list, lower_diag.edge_list);
+        assert_eq!(full.edge_list, upper_col.edge_list);
+
+        let graph = AdjacencyList::<usize, f64>::try_from(full.edge_list).unwrap();
+        assert_eq!(
+            graph.weight(EdgeId::new_unchecked(id(3), id(1))),
+            Some(&5.0)
+        );
+    }
+
+    #[test]
+    fn tsplib_distances() {
+        let content = "TYPE: TSP\nDIMENSION: 2\nEDGE_WEIGHT_TYPE: ATT\n\
+                       NODE_COORD_SECTION\n1 0 0\n2 10 0\nEOF\n";
+        let graph =
+            AdjacencyList::<usize, f64>::try_from(Tsplib::from_str(content).unwrap().edge_list)
+                .unwrap();
+        // sqrt(10) is rounded up
+        assert_eq!(
+            graph.weight(EdgeId::new_unchecked(id(0), id(1))),
+            Some(&4.0)
+        );
+
+        let content = "TYPE: TSP\nDIMENSION: 2\nEDGE_WEIGHT_TYPE: GEO\n\
+                       NODE_COORD_SECTION\n1 0.0 0.0\n2 0.0 1.0\nEOF\n";
+        let graph =
+            AdjacencyList::<usize, f64>::try_from(Tsplib::from_str(content).unwrap().edge_list)
+                .unwrap();
+        // one degree on the equator is about 111.32 km
+        assert_eq!(
+            graph.weight(EdgeId::new_unchecked(id(0), id(1))),
+            Some(&112.0)
+        );
+    }
+
+    #[test]
+    fn tsplib_errors() {
+        let result = Tsplib::from_str("TYPE: ATSP\nDIMENSION: 2\n");
+        assert!(matches!(
+            result,
+            Err(GraphError::Parse {
+                line: 1,
+                column: 7,
+                kind: ParseErrorKind::Expected(_),
+                ..
+            })
+        ));
+
+        let result = Tsplib::from_str(
+            "DIMENSION: 2\nEDGE_WEIGHT_TYPE: EUC_2D\nNODE_COORD_SECTION\n1 0 0\n3 1 1\n",
+        );
+        assert!(matches!(
+            result,
+            Err(GraphError::Parse {
+                line: 5,
+                column: 1,
+                kind: ParseErrorKind::NodeOutOfRange { node_count: 2 },
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn tour_round_trip() {
+        let content =
+            "NAME : square.tour\nTYPE : TOUR\nDIMENSION : 4\nTOUR_SECTION\n1\n3 2\n4\n-1\nEOF\n";
+        let tour = Tour::from_str(content).unwrap();
+        assert_eq!(tour.nodes, vec![0, 2, 1, 3]);
+
+        let route: Route<AdjacencyList<usize, f64>> = Route::from(tour.clone());
+        assert_eq!(route.node_ids(), &vec![id(0), id(2), id(1), id(3)]);
+
+        let written = Tour {
+            name: tour.name.clone(),
+            ..Tour::from(&route)
+        }
+        .to_string();
+        assert_eq!(Tour::from_str(&written).unwrap(), tour);
+
+        let result = Tour::from_str("DIMENSION : 2\nTOUR_SECTION\n1 3\n-1\n");
+        assert!(matches!(
+            result,
+            Err(GraphError::Parse {
+                line: 3,
+                column: 3,
+                kind: ParseErrorKind::NodeOutOfRange { node_count: 2 },
+                ..
+            })
+        ));
+    }
+}